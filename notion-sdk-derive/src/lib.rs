@@ -0,0 +1,337 @@
+//!
+//! # Notion SDK Derive
+//!
+//! `#[derive(NotionPage)]` maps the fields of a struct to the properties of database pages,
+//! enable it with the `derive` feature of `notion-sdk`.
+//!
+//! Every field needs a `#[notion(...)]` attribute:
+//! - `kind = "..."` the property type, as named by the Notion API: `title`, `rich_text`, `number`,
+//!   `select`, `status`, `multi_select`, `date`, `people`, `files`, `checkbox`, `url`, `email`,
+//!   `phone_number`, `formula`, `relation`, `rollup`, `created_time`, `created_by`,
+//!   `last_edited_time` or `last_edited_by`
+//! - `name = "..."` the property name, the field name by default
+//! - `read_only` never write the field back, `files`, `formula`, `rollup` and
+//!   the created/last edited properties are always read only
+//! - `skip` not a property, filled with [`Default`] when reading
+//!
+//! `Option` fields accept missing and empty properties, other fields make reading fail.
+//! Exactly one field must be the `title`, like every database has one title property.
+//!
+//! ## Examples
+//! ```rust
+//! use notion_sdk::database::date::DateValue;
+//! use notion_sdk::pages::NotionPage;
+//!
+//! #[derive(NotionPage)]
+//! struct Task {
+//!     #[notion(name = "Name", kind = "title")]
+//!     name: String,
+//!     #[notion(name = "Status", kind = "status")]
+//!     status: Option<String>,
+//!     #[notion(name = "Estimate", kind = "number")]
+//!     estimate: Option<f64>,
+//!     #[notion(name = "Tags", kind = "multi_select")]
+//!     tags: Vec<String>,
+//!     #[notion(name = "Due", kind = "date")]
+//!     due: Option<DateValue>,
+//!     #[notion(skip)]
+//!     dirty: bool,
+//! }
+//!
+//! let task = Task {
+//!     name: "Write docs".to_string(),
+//!     status: Some("In progress".to_string()),
+//!     estimate: None,
+//!     tags: vec!["docs".to_string()],
+//!     due: None,
+//!     dirty: false,
+//! };
+//! let properties = task.to_properties();
+//! assert_eq!(properties.properties.len(), 5);
+//! ```
+//!
+//! ## Compile errors
+//! A field type which does not fit the kind of its property:
+//! ```compile_fail,E0277
+//! use notion_sdk::pages::NotionPage;
+//!
+//! #[derive(NotionPage)]
+//! struct Task {
+//!     #[notion(name = "Name", kind = "title")]
+//!     name: String,
+//!     #[notion(name = "Estimate", kind = "number")]
+//!     estimate: Vec<String>,
+//! }
+//! ```
+//! An attribute other than `name`, `kind`, `read_only` and `skip`:
+//! ```compile_fail
+//! use notion_sdk::pages::NotionPage;
+//!
+//! #[derive(NotionPage)]
+//! struct Task {
+//!     #[notion(name = "Name", kind = "title", rename = "Title")]
+//!     name: String,
+//! }
+//! ```
+//! No title field:
+//! ```compile_fail
+//! use notion_sdk::pages::NotionPage;
+//!
+//! #[derive(NotionPage)]
+//! struct Task {
+//!     #[notion(name = "Estimate", kind = "number")]
+//!     estimate: Option<f64>,
+//! }
+//! ```
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote, quote_spanned};
+use std::collections::HashSet;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Ident, LitStr};
+
+/// property type names of the Notion API, their marker types and whether they can be written
+const KINDS: &[(&str, &str, bool)] = &[
+    ("title", "Title", true),
+    ("rich_text", "Text", true),
+    ("number", "Number", true),
+    ("select", "Select", true),
+    ("status", "Status", true),
+    ("multi_select", "MultiSelect", true),
+    ("date", "Date", true),
+    ("people", "People", true),
+    ("files", "Files", false),
+    ("checkbox", "Checkbox", true),
+    ("url", "Url", true),
+    ("email", "Email", true),
+    ("phone_number", "PhoneNumber", true),
+    ("formula", "Formula", false),
+    ("relation", "Relation", true),
+    ("rollup", "Rollup", false),
+    ("created_time", "CreatedTime", false),
+    ("created_by", "CreatedBy", false),
+    ("last_edited_time", "LastEditedTime", false),
+    ("last_edited_by", "LastEditedBy", false),
+];
+
+/// See the [crate documentation](crate).
+#[proc_macro_derive(NotionPage, attributes(notion))]
+pub fn derive_notion_page(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+enum FieldKind {
+    Skip,
+    Property {
+        name: LitStr,
+        kind: Ident,
+        writable: bool,
+    },
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    Span::call_site(),
+                    "NotionPage can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "NotionPage can only be derived for structs",
+            ))
+        }
+    };
+    let mut names = HashSet::new();
+    let mut title: Option<LitStr> = None;
+    let mut reads = vec![];
+    let mut writes = vec![];
+    let mut errors: Option<Error> = None;
+    for field in fields {
+        let ident = field.ident.as_ref().expect("named field");
+        let ty = &field.ty;
+        let kind = match parse_field(field, ident) {
+            Ok(kind) => kind,
+            Err(error) => {
+                combine(&mut errors, error);
+                continue;
+            }
+        };
+        match kind {
+            FieldKind::Skip => reads.push(quote! {
+                #ident: ::core::default::Default::default()
+            }),
+            FieldKind::Property {
+                name,
+                kind,
+                writable,
+            } => {
+                if !names.insert(name.value()) {
+                    let message = format!("property `{}` is mapped twice", name.value());
+                    combine(&mut errors, Error::new(name.span(), message));
+                    continue;
+                }
+                if kind == "Title" {
+                    match &title {
+                        Some(first) => {
+                            let message = format!(
+                                "a database has a single title property, `{}` is already the title",
+                                first.value()
+                            );
+                            combine(&mut errors, Error::new(name.span(), message));
+                        }
+                        None => title = Some(name.clone()),
+                    }
+                }
+                let kind = quote! { ::notion_sdk::database::kind::#kind };
+                reads.push(quote_spanned! {ty.span()=>
+                    #ident: match page.get_property(#name).map_or_else(
+                        <#ty as ::notion_sdk::database::kind::FromProperty<#kind>>::missing,
+                        <#ty as ::notion_sdk::database::kind::FromProperty<#kind>>::from_property,
+                    ) {
+                        ::core::option::Option::Some(value) => value,
+                        ::core::option::Option::None => {
+                            return ::core::result::Result::Err(
+                                ::notion_sdk::error::Error::InvalidPageProperty {
+                                    name: ::std::string::String::from(#name),
+                                    kind: <#kind as ::notion_sdk::database::kind::PropertyKind>::NAME,
+                                },
+                            )
+                        }
+                    }
+                });
+                if writable {
+                    writes.push(quote_spanned! {ty.span()=>
+                        properties.insert(
+                            ::std::string::String::from(#name),
+                            <#ty as ::notion_sdk::database::kind::IntoProperty<#kind>>::to_property(&self.#ident),
+                        );
+                    });
+                }
+            }
+        }
+    }
+    // a field which failed to parse may be the title
+    if title.is_none() && errors.is_none() {
+        combine(
+            &mut errors,
+            Error::new(
+                input.ident.span(),
+                "missing a `#[notion(kind = \"title\")]` field, every database has a title property",
+            ),
+        );
+    }
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::notion_sdk::pages::NotionPage for #ident #ty_generics #where_clause {
+            fn from_page(
+                page: &::notion_sdk::pages::Page,
+            ) -> ::core::result::Result<Self, ::notion_sdk::error::Error> {
+                ::core::result::Result::Ok(#ident {
+                    #(#reads,)*
+                })
+            }
+            fn to_properties(&self) -> ::notion_sdk::database::properties::Properties {
+                #[allow(unused_mut)]
+                let mut properties = ::std::collections::HashMap::new();
+                #(#writes)*
+                ::notion_sdk::database::properties::Properties { properties }
+            }
+        }
+
+        impl #impl_generics ::core::convert::TryFrom<&::notion_sdk::pages::Page> for #ident #ty_generics #where_clause {
+            type Error = ::notion_sdk::error::Error;
+            fn try_from(page: &::notion_sdk::pages::Page) -> ::core::result::Result<Self, Self::Error> {
+                <Self as ::notion_sdk::pages::NotionPage>::from_page(page)
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<&#ident #ty_generics> for ::notion_sdk::database::properties::Properties #where_clause {
+            fn from(page: &#ident #ty_generics) -> Self {
+                ::notion_sdk::pages::NotionPage::to_properties(page)
+            }
+        }
+    })
+}
+
+/// report every invalid field at once
+fn combine(errors: &mut Option<Error>, error: Error) {
+    match errors {
+        Some(errors) => errors.combine(error),
+        None => *errors = Some(error),
+    }
+}
+
+fn parse_field(field: &syn::Field, ident: &Ident) -> syn::Result<FieldKind> {
+    let mut name = None;
+    let mut kind: Option<LitStr> = None;
+    let mut read_only = false;
+    let mut skip = false;
+    let mut found = false;
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("notion")) {
+        found = true;
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                name = Some(meta.value()?.parse::<LitStr>()?);
+            } else if meta.path.is_ident("kind") {
+                kind = Some(meta.value()?.parse::<LitStr>()?);
+            } else if meta.path.is_ident("read_only") {
+                read_only = true;
+            } else if meta.path.is_ident("skip") {
+                skip = true;
+            } else {
+                return Err(meta.error(
+                    "unknown notion attribute, expected `name`, `kind`, `read_only` or `skip`",
+                ));
+            }
+            Ok(())
+        })?;
+    }
+    if skip {
+        return Ok(FieldKind::Skip);
+    }
+    let Some(kind) = kind else {
+        let span = if found {
+            field
+                .attrs
+                .iter()
+                .find(|a| a.path().is_ident("notion"))
+                .span()
+        } else {
+            ident.span()
+        };
+        return Err(Error::new(
+            span,
+            format!("missing `#[notion(kind = \"...\")]` on `{ident}`, or `#[notion(skip)]` if it is not a property"),
+        ));
+    };
+    let Some((_, marker, writable)) = KINDS.iter().find(|(api, _, _)| *api == kind.value()) else {
+        let known: Vec<&str> = KINDS.iter().map(|(api, _, _)| *api).collect();
+        return Err(Error::new(
+            kind.span(),
+            format!(
+                "unknown property kind `{}`, expected one of {}",
+                kind.value(),
+                known.join(", ")
+            ),
+        ));
+    };
+    Ok(FieldKind::Property {
+        name: name.unwrap_or_else(|| LitStr::new(&ident.to_string(), ident.span())),
+        kind: format_ident!("{}", marker),
+        writable: *writable && !read_only,
+    })
+}
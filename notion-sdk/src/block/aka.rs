@@ -0,0 +1,167 @@
+use crate::block::id::BlockId;
+use crate::block::{Block, BlockCommon};
+
+impl Block {
+    /// fields shared by every block, `None` for [`Block::Unknown`]
+    pub fn common(&self) -> Option<&BlockCommon> {
+        use Block::*;
+        match self {
+            Paragraph { common, .. }
+            | Heading1 { common, .. }
+            | Heading2 { common, .. }
+            | Heading3 { common, .. }
+            | CallOut { common, .. }
+            | Quote { common, .. }
+            | BulletedListItem { common, .. }
+            | NumberedListItem { common, .. }
+            | ToDo { common, .. }
+            | Toggle { common, .. }
+            | Code { common, .. }
+            | ChildPage { common, .. }
+            | ChildDatabase { common, .. }
+            | Embed { common, .. }
+            | Image { common, .. }
+            | Video { common, .. }
+            | File { common, .. }
+            | Pdf { common, .. }
+            | Bookmark { common, .. }
+            | Equation { common, .. }
+            | Divider { common }
+            | TableOfContents { common, .. }
+            | Breadcrumb { common }
+            | ColumnList { common, .. }
+            | Column { common, .. }
+            | LinkPreview { common, .. }
+            | Template { common, .. }
+            | LinkToPage { common, .. }
+            | Table { common, .. }
+            | SyncedBlock { common, .. }
+            | TableRow { common, .. }
+            | Unsupported { common } => Some(common),
+            Unknown => None,
+        }
+    }
+    /// block id, `None` for [`Block::Unknown`]
+    pub fn id(&self) -> Option<&BlockId> {
+        self.common().map(|common| &common.id)
+    }
+    /// whether Notion holds children for this block inside the same page,
+    /// child pages and databases are separate pages
+    pub fn has_nested_children(&self) -> bool {
+        match self {
+            Block::ChildPage { .. } | Block::ChildDatabase { .. } => false,
+            block => block.common().is_some_and(|common| common.has_children),
+        }
+    }
+    /// children the block was fetched with, see [`NotionApi::fetch_block_tree`](crate::NotionApi::fetch_block_tree)
+    pub fn children(&self) -> Option<&[Block]> {
+        use Block::*;
+        match self {
+            Paragraph {
+                paragraph: text, ..
+            }
+            | Quote { quote: text, .. }
+            | BulletedListItem {
+                bulleted_list_item: text,
+                ..
+            }
+            | NumberedListItem {
+                numbered_list_item: text,
+                ..
+            }
+            | Toggle { toggle: text, .. } => text.children.as_deref(),
+            Heading1 {
+                heading_1: heading, ..
+            }
+            | Heading2 {
+                heading_2: heading, ..
+            }
+            | Heading3 {
+                heading_3: heading, ..
+            } => heading.children.as_deref(),
+            CallOut { call_out, .. } => call_out.children.as_deref(),
+            ToDo { to_do, .. } => to_do.children.as_deref(),
+            ColumnList { column_list, .. } => Some(&column_list.children),
+            Column { column, .. } => Some(&column.children),
+            Template { template, .. } => Some(&template.children),
+            Table { table, .. } => Some(&table.children),
+            SyncedBlock { synced_block, .. } => Some(&synced_block.children),
+            _ => None,
+        }
+    }
+    /// replace the children of the block, ignored by blocks which can not hold any
+    pub fn set_children(&mut self, children: Vec<Block>) {
+        use Block::*;
+        match self {
+            Paragraph {
+                paragraph: text, ..
+            }
+            | Quote { quote: text, .. }
+            | BulletedListItem {
+                bulleted_list_item: text,
+                ..
+            }
+            | NumberedListItem {
+                numbered_list_item: text,
+                ..
+            }
+            | Toggle { toggle: text, .. } => text.children = Some(children),
+            Heading1 {
+                heading_1: heading, ..
+            }
+            | Heading2 {
+                heading_2: heading, ..
+            }
+            | Heading3 {
+                heading_3: heading, ..
+            } => heading.children = Some(children),
+            CallOut { call_out, .. } => call_out.children = Some(children),
+            ToDo { to_do, .. } => to_do.children = Some(children),
+            ColumnList { column_list, .. } => column_list.children = children,
+            Column { column, .. } => column.children = children,
+            Template { template, .. } => template.children = children,
+            Table { table, .. } => table.children = children,
+            SyncedBlock { synced_block, .. } => synced_block.children = children,
+            _ => {}
+        }
+    }
+    /// the `type` of the block as named by Notion, e.g. `heading_1`
+    pub fn block_type(&self) -> &'static str {
+        use Block::*;
+        match self {
+            Paragraph { .. } => "paragraph",
+            Heading1 { .. } => "heading_1",
+            Heading2 { .. } => "heading_2",
+            Heading3 { .. } => "heading_3",
+            CallOut { .. } => "callout",
+            Quote { .. } => "quote",
+            BulletedListItem { .. } => "bulleted_list_item",
+            NumberedListItem { .. } => "numbered_list_item",
+            ToDo { .. } => "to_do",
+            Toggle { .. } => "toggle",
+            Code { .. } => "code",
+            ChildPage { .. } => "child_page",
+            ChildDatabase { .. } => "child_database",
+            Embed { .. } => "embed",
+            Image { .. } => "image",
+            Video { .. } => "video",
+            File { .. } => "file",
+            Pdf { .. } => "pdf",
+            Bookmark { .. } => "bookmark",
+            Equation { .. } => "equation",
+            Divider { .. } => "divider",
+            TableOfContents { .. } => "table_of_contents",
+            Breadcrumb { .. } => "breadcrumb",
+            ColumnList { .. } => "column_list",
+            Column { .. } => "column",
+            LinkPreview { .. } => "link_preview",
+            Template { .. } => "template",
+            LinkToPage { .. } => "link_to_page",
+            Table { .. } => "table",
+            SyncedBlock { .. } => "synced_block",
+            TableRow { .. } => "table_row",
+            Unsupported { .. } => "unsupported",
+            Unknown => "unknown",
+        }
+    }
+}
@@ -1,30 +1,472 @@
 use crate::block::id::BlockId;
-use crate::block::UpdateBlock;
-use crate::pages::id::PageId;
+use crate::block::{
+    Block, BlockTreeOptions, CreateBlock, NewBlock, UpdateBlock, MAX_APPEND_CHILDREN,
+};
+use crate::pagination::{collect_all, paginate, FromObject, ListResponse, Paging};
 use crate::{Error, NotionApi, Object};
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use std::collections::HashMap;
 
 const ROUTER: &str = "blocks";
 
+/// Levels of children Notion accepts below the blocks of one append request
+const MAX_NESTING: usize = 2;
+
+/// Children held back from a request because they are nested too deep,
+/// `path` leads from the appended block to their parent.
+struct Deferred {
+    path: Vec<usize>,
+    children: Vec<NewBlock>,
+}
+
+fn defer_nested(block: &mut NewBlock, path: &mut Vec<usize>, deferred: &mut Vec<Deferred>) {
+    if path.len() == MAX_NESTING {
+        let children = block.take_children();
+        if !children.is_empty() {
+            deferred.push(Deferred {
+                path: path.clone(),
+                children,
+            });
+        }
+        return;
+    }
+    for (index, child) in block.children_mut().into_iter().flatten().enumerate() {
+        path.push(index);
+        defer_nested(child, path, deferred);
+        path.pop();
+    }
+}
+
+impl NotionApi {
+    /// Retrieve a block
+    pub async fn block_retrieve(&self, id: BlockId) -> Result<Block, Error> {
+        self.raw_block_retrieve(id)
+            .await
+            .and_then(FromObject::from_object)
+    }
+    /// Retrieve block children, at most 100 per request
+    pub async fn block_children(
+        &self,
+        id: BlockId,
+        paging: Option<Paging>,
+    ) -> Result<ListResponse<Block>, Error> {
+        self.raw_block_children(id, paging)
+            .await
+            .and_then(FromObject::from_object)
+    }
+    /// Yield every child of a block, fetching more as the stream is polled
+    pub fn block_children_stream(
+        &self,
+        id: BlockId,
+    ) -> impl Stream<Item = Result<Block, Error>> + '_ {
+        paginate(move |start_cursor| {
+            let id = id.clone();
+            let paging = Paging {
+                start_cursor,
+                page_size: None,
+            };
+            async move { self.block_children(id, Some(paging)).await }
+        })
+    }
+    /// Fetch the children of a block or page together with all their descendants,
+    /// level by level with at most [`BlockTreeOptions::concurrency`] blocks loading at once.
+    ///
+    /// Child pages and child databases are not descended into.
+    pub async fn fetch_block_tree<I: Into<BlockId>>(
+        &self,
+        id: I,
+        options: BlockTreeOptions,
+    ) -> Result<Vec<Block>, Error> {
+        let root = id.into();
+        let mut children_of: HashMap<BlockId, Vec<Block>> = HashMap::new();
+        let mut level = vec![root.clone()];
+        let mut depth = 0;
+        while !level.is_empty() && options.max_depth.map_or(true, |max| depth < max) {
+            let fetched: Vec<(BlockId, Vec<Block>)> = stream::iter(level)
+                .map(|id| async move {
+                    let children =
+                        collect_all(self.block_children_stream(id.clone()), None).await?;
+                    Ok::<_, Error>((id, children))
+                })
+                .buffer_unordered(options.concurrency.max(1))
+                .try_collect()
+                .await?;
+            level = fetched
+                .iter()
+                .flat_map(|(_, children)| children)
+                .filter(|block| block.has_nested_children())
+                .filter_map(|block| block.id().cloned())
+                .collect();
+            children_of.extend(fetched);
+            depth += 1;
+        }
+        let mut blocks = children_of.remove(&root).unwrap_or_default();
+        attach_children(&mut blocks, &mut children_of);
+        Ok(blocks)
+    }
+    /// Append block children to a block or page.
+    ///
+    /// More than [`MAX_APPEND_CHILDREN`] children are sent in several requests,
+    /// each one inserted after the last block created by the previous one.
+    /// Notion takes two levels of nesting per request, deeper children are appended
+    /// to their parent once it was created.
+    /// When a request fails after others succeeded, the blocks already created come back in
+    /// [`Error::PartiallyAppended`].
+    pub async fn block_append_children<I: Into<BlockId>>(
+        &self,
+        id: I,
+        children: CreateBlock,
+    ) -> Result<ListResponse<Block>, Error> {
+        let id = id.into();
+        let CreateBlock {
+            children,
+            mut after,
+        } = children;
+        let mut appended = ListResponse {
+            results: Vec::with_capacity(children.len()),
+            next_cursor: None,
+            has_more: false,
+        };
+        let mut children = children.into_iter().peekable();
+        while children.peek().is_some() {
+            let mut chunk = CreateBlock {
+                children: children.by_ref().take(MAX_APPEND_CHILDREN).collect(),
+                after: after.clone(),
+            };
+            let mut deferred = vec![];
+            for (index, block) in chunk.children.iter_mut().enumerate() {
+                let mut nested = vec![];
+                defer_nested(block, &mut vec![], &mut nested);
+                deferred.extend(nested.into_iter().map(|nested| (index, nested)));
+            }
+            let response: ListResponse<Block> = match self
+                .raw_block_append_children(id.clone(), chunk)
+                .await
+                .and_then(FromObject::from_object)
+            {
+                Ok(response) => response,
+                Err(source) if appended.results.is_empty() => return Err(source),
+                Err(source) => {
+                    return Err(Error::PartiallyAppended {
+                        appended: appended.results,
+                        source: Box::new(source),
+                    })
+                }
+            };
+            if after.is_some() {
+                after = response.results.last().and_then(Block::id).cloned();
+            }
+            let created = response.results.clone();
+            appended.results.extend(response.results);
+            appended.next_cursor = response.next_cursor;
+            appended.has_more = response.has_more;
+            for (index, nested) in deferred {
+                if let Err(source) = self.append_nested(&created, index, nested).await {
+                    return Err(Error::PartiallyAppended {
+                        appended: appended.results,
+                        source: Box::new(source),
+                    });
+                }
+            }
+        }
+        Ok(appended)
+    }
+    /// append children held back by [`defer_nested`] below block `index` of a response
+    async fn append_nested(
+        &self,
+        created: &[Block],
+        index: usize,
+        nested: Deferred,
+    ) -> Result<(), Error> {
+        let mut parent = created.get(index).and_then(Block::id).cloned();
+        for &position in &nested.path {
+            let Some(id) = parent else { break };
+            let children = collect_all(self.block_children_stream(id), None).await?;
+            parent = children.get(position).and_then(Block::id).cloned();
+        }
+        let Some(id) = parent else {
+            return Err(Error::UnexpectedResponse {
+                response: Box::new(Object::List {
+                    list: ListResponse {
+                        results: created
+                            .iter()
+                            .cloned()
+                            .map(|block| Object::Block { block })
+                            .collect(),
+                        next_cursor: None,
+                        has_more: false,
+                    },
+                }),
+            });
+        };
+        let children = CreateBlock {
+            children: nested.children,
+            after: None,
+        };
+        Box::pin(self.block_append_children(id, children)).await?;
+        Ok(())
+    }
+    /// Delete a block
+    pub async fn block_delete(&self, id: BlockId) -> Result<Block, Error> {
+        self.raw_block_delete(id)
+            .await
+            .and_then(FromObject::from_object)
+    }
+    /// Update a block
+    pub async fn block_update(&self, id: BlockId, db: UpdateBlock) -> Result<Block, Error> {
+        self.raw_block_update(id, db)
+            .await
+            .and_then(FromObject::from_object)
+    }
+}
+
 impl NotionApi {
-    /// Retrieve a user
-    pub async fn block_retrieve(&self, id: BlockId) -> Result<Object, Error> {
+    pub async fn raw_block_retrieve(&self, id: BlockId) -> Result<Object, Error> {
         let u = format!("{}/{}/{}", self.base_path, ROUTER, id);
         self.request(self.client.get(u)).await
     }
-    pub async fn block_children(&self, id: BlockId) -> Result<Object, Error> {
+    pub async fn raw_block_children(
+        &self,
+        id: BlockId,
+        paging: Option<Paging>,
+    ) -> Result<Object, Error> {
         let u = format!("{}/{}/{}/children", self.base_path, ROUTER, id);
-        self.request(self.client.get(u)).await
+        self.request(self.client.get(u).query(&paging.unwrap_or_default()))
+            .await
     }
-    pub async fn block_append_children(&self, id: PageId) -> Result<Object, Error> {
-        let u = format!("{}/{}/{}/children", self.base_path, ROUTER, id);
-        self.request(self.client.patch(u)).await
+    pub async fn raw_block_append_children<I: Into<BlockId>>(
+        &self,
+        id: I,
+        children: CreateBlock,
+    ) -> Result<Object, Error> {
+        let u = format!("{}/{}/{}/children", self.base_path, ROUTER, id.into());
+        self.request_not_idempotent(self.client.patch(u).json(&children))
+            .await
     }
-    pub async fn block_delete(&self, id: BlockId) -> Result<Object, Error> {
+    pub async fn raw_block_delete(&self, id: BlockId) -> Result<Object, Error> {
         let u = format!("{}/{}/{}", self.base_path, ROUTER, id);
         self.request(self.client.delete(u)).await
     }
-    pub async fn block_update(&self, id: BlockId, db: UpdateBlock) -> Result<Object, Error> {
+    pub async fn raw_block_update(&self, id: BlockId, db: UpdateBlock) -> Result<Object, Error> {
         let u = format!("{}/{}/{}", self.base_path, ROUTER, id);
         self.request(self.client.patch(u).json(&db)).await
     }
 }
+
+fn attach_children(blocks: &mut [Block], children_of: &mut HashMap<BlockId, Vec<Block>>) {
+    for block in blocks {
+        let children = block.id().and_then(|id| children_of.remove(id));
+        if let Some(mut children) = children {
+            attach_children(&mut children, children_of);
+            block.set_children(children);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::builder::{bulleted_list_item, paragraph};
+    use crate::fixtures::FakeBlocks;
+
+    const PAGE: &str = "3f8d5d7e-6c0a-4d9b-9a51-9f3c2b1e8a10";
+
+    fn page() -> BlockId {
+        PAGE.parse().unwrap()
+    }
+
+    fn paragraphs(n: usize) -> Vec<NewBlock> {
+        (0..n).map(|i| paragraph(i.to_string())).collect()
+    }
+
+    fn ids(blocks: &[Block]) -> Vec<String> {
+        blocks
+            .iter()
+            .filter_map(Block::id)
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    /// ids of the blocks with their children, as fetched
+    fn fetched_tree(blocks: &[Block]) -> Vec<(String, Vec<String>)> {
+        let mut tree = vec![];
+        for block in blocks {
+            let children = block.children().unwrap_or_default();
+            tree.push((block.id().unwrap().to_string(), ids(children)));
+            tree.extend(fetched_tree(children));
+        }
+        tree
+    }
+
+    /// ids of the blocks below `id` with their children, as stored
+    fn stored_tree(fake: &FakeBlocks, id: &str, depth: usize) -> Vec<(String, Vec<String>)> {
+        let mut tree = vec![];
+        for child in fake.child_ids(id) {
+            if depth == 1 {
+                tree.push((child, vec![]));
+                continue;
+            }
+            tree.push((child.clone(), fake.child_ids(&child)));
+            tree.extend(stored_tree(fake, &child, depth - 1));
+        }
+        tree
+    }
+
+    /// a page with a few children on each of four levels
+    async fn nested_page(fake: &FakeBlocks, notion: &NotionApi) {
+        let level = |n: usize, children: Vec<NewBlock>| {
+            (0..n)
+                .map(|i| bulleted_list_item(i.to_string()).children(children.clone()))
+                .collect::<Vec<_>>()
+        };
+        let children = level(3, level(2, level(3, vec![paragraph("leaf")])));
+        let children = CreateBlock {
+            children,
+            after: None,
+        };
+        notion
+            .block_append_children(page(), children)
+            .await
+            .unwrap();
+        assert!(fake.appends().len() > 1);
+    }
+
+    #[tokio::test]
+    async fn trees_are_fetched_across_pages_of_children() {
+        let (_server, notion, fake) = FakeBlocks::start(2).await;
+        nested_page(&fake, &notion).await;
+        let options = BlockTreeOptions {
+            max_depth: None,
+            concurrency: 2,
+        };
+        let blocks = notion.fetch_block_tree(page(), options).await.unwrap();
+        assert_eq!(ids(&blocks), fake.child_ids(PAGE));
+        assert_eq!(fetched_tree(&blocks), stored_tree(&fake, PAGE, 4));
+    }
+
+    #[tokio::test]
+    async fn trees_stop_at_the_maximum_depth() {
+        let (_server, notion, fake) = FakeBlocks::start(2).await;
+        nested_page(&fake, &notion).await;
+        let options = BlockTreeOptions {
+            max_depth: Some(2),
+            concurrency: 1,
+        };
+        let blocks = notion.fetch_block_tree(page(), options).await.unwrap();
+        assert_eq!(fetched_tree(&blocks), stored_tree(&fake, PAGE, 2));
+    }
+
+    #[tokio::test]
+    async fn more_than_a_hundred_children_are_sent_in_chunks() {
+        let (_server, notion, fake) = FakeBlocks::start(100).await;
+        let appended = notion
+            .block_append_children(
+                page(),
+                CreateBlock {
+                    children: paragraphs(250),
+                    after: None,
+                },
+            )
+            .await
+            .unwrap();
+        let appends = fake.appends();
+        let sizes: Vec<usize> = appends
+            .iter()
+            .map(|(_, body)| body["children"].as_array().unwrap().len())
+            .collect();
+        assert_eq!(sizes, vec![100, 100, 50]);
+        assert!(appends.iter().all(|(_, body)| body.get("after").is_none()));
+        assert_eq!(ids(&appended.results), fake.child_ids(PAGE));
+    }
+
+    #[tokio::test]
+    async fn chunks_are_inserted_one_after_the_other() {
+        let (_server, notion, fake) = FakeBlocks::start(100).await;
+        let edges = CreateBlock {
+            children: vec![paragraph("first"), paragraph("last")],
+            after: None,
+        };
+        notion.block_append_children(page(), edges).await.unwrap();
+        let first = fake.child_ids(PAGE)[0].clone();
+        let appended = notion
+            .block_append_children(
+                page(),
+                CreateBlock {
+                    children: paragraphs(250),
+                    after: Some(first.parse().unwrap()),
+                },
+            )
+            .await
+            .unwrap();
+        let afters: Vec<String> = fake.appends()[1..]
+            .iter()
+            .map(|(_, body)| body["after"].as_str().unwrap().to_string())
+            .collect();
+        let results = ids(&appended.results);
+        assert_eq!(
+            afters,
+            vec![first, results[99].clone(), results[199].clone()]
+        );
+        let children = fake.child_ids(PAGE);
+        assert_eq!(children.len(), 252);
+        assert_eq!(children[1..251], results[..]);
+    }
+
+    #[tokio::test]
+    async fn failing_chunks_return_the_blocks_already_appended() {
+        let (_server, notion, fake) = FakeBlocks::start(100).await;
+        fake.fail_append(1);
+        let error = notion
+            .block_append_children(
+                page(),
+                CreateBlock {
+                    children: paragraphs(250),
+                    after: None,
+                },
+            )
+            .await
+            .unwrap_err();
+        let Error::PartiallyAppended { appended, source } = error else {
+            panic!("expected a partial append, got {error:?}");
+        };
+        assert_eq!(ids(&appended), fake.child_ids(PAGE));
+        assert_eq!(appended.len(), 100);
+        assert!(matches!(*source, Error::ApiError { .. }), "{source:?}");
+        assert_eq!(fake.appends().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn children_nested_too_deep_are_appended_to_their_parent() {
+        let (_server, notion, fake) = FakeBlocks::start(100).await;
+        let tree = bulleted_list_item("a").children(vec![bulleted_list_item("b")
+            .children(vec![bulleted_list_item("c").children(vec![
+                bulleted_list_item("d").children(vec![paragraph("e")]),
+            ])])]);
+        let appended = notion
+            .block_append_children(
+                page(),
+                CreateBlock {
+                    children: vec![tree],
+                    after: None,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(appended.results.len(), 1);
+
+        let appends = fake.appends();
+        assert_eq!(appends.len(), 2);
+        let c = &appends[0].1["children"][0]["bulleted_list_item"]["children"][0]
+            ["bulleted_list_item"]["children"][0]["bulleted_list_item"];
+        assert_eq!(c["rich_text"][0]["plain_text"], "c");
+        assert!(c.get("children").is_none(), "{c}");
+
+        let path = |ids: &[usize]| {
+            ids.iter().fold(PAGE.to_string(), |parent, &index| {
+                fake.child_ids(&parent)[index].clone()
+            })
+        };
+        assert_eq!(appends[1].0, path(&[0, 0, 0]));
+        assert_eq!(fake.child_ids(&path(&[0, 0, 0, 0])).len(), 1);
+    }
+}
@@ -0,0 +1,371 @@
+//!
+//! # Block Builder
+//!
+//! Short constructors for [`NewBlock`], chain the methods of [`NewBlock`] to style them.
+//!
+//! ## Examples
+//! ```rust
+//! use notion_sdk::block::builder::*;
+//! use notion_sdk::block::CodeLanguage;
+//! use notion_sdk::common::rich_text::RichText;
+//! use notion_sdk::rich_text;
+//! let blocks = vec![
+//!     heading_2("Release notes"),
+//!     paragraph("Everything below is ").children(vec![
+//!         todo(true, "write the changelog"),
+//!         todo(false, rich_text!["tag ", RichText::text("v1.0").code()]),
+//!     ]),
+//!     paragraph("important").bold(),
+//!     code(CodeLanguage::Rust, "fn main() {}"),
+//!     callout("💡", "Notion only keeps 100 children per request"),
+//! ];
+//! ```
+use crate::block::{
+    BookmarkFields, CodeFields, CodeLanguage, EmbedFields, EmptyObject, Equation, NewBlock,
+    NewCallOut, NewColumn, NewColumnList, NewHeading, NewTable, NewTextAndChildren, NewToDo,
+    TableOfContents, TableRowFields,
+};
+use crate::common::file::{ExternalFileObject, FileObject, FileOrEmojiObject};
+use crate::common::rich_text::{IntoRichText, RichText, TextColor};
+
+fn text_and_children<T: IntoRichText>(text: T) -> NewTextAndChildren {
+    NewTextAndChildren {
+        rich_text: text.into_rich_text(),
+        ..Default::default()
+    }
+}
+
+fn heading<T: IntoRichText>(text: T) -> NewHeading {
+    NewHeading {
+        rich_text: text.into_rich_text(),
+        ..Default::default()
+    }
+}
+
+fn external<T: Into<String>>(url: T) -> FileObject {
+    FileObject::External {
+        external: ExternalFileObject { url: url.into() },
+    }
+}
+
+pub fn paragraph<T: IntoRichText>(text: T) -> NewBlock {
+    NewBlock::Paragraph {
+        paragraph: text_and_children(text),
+    }
+}
+
+pub fn heading_1<T: IntoRichText>(text: T) -> NewBlock {
+    NewBlock::Heading1 {
+        heading_1: heading(text),
+    }
+}
+
+pub fn heading_2<T: IntoRichText>(text: T) -> NewBlock {
+    NewBlock::Heading2 {
+        heading_2: heading(text),
+    }
+}
+
+pub fn heading_3<T: IntoRichText>(text: T) -> NewBlock {
+    NewBlock::Heading3 {
+        heading_3: heading(text),
+    }
+}
+
+pub fn quote<T: IntoRichText>(text: T) -> NewBlock {
+    NewBlock::Quote {
+        quote: text_and_children(text),
+    }
+}
+
+pub fn bulleted_list_item<T: IntoRichText>(text: T) -> NewBlock {
+    NewBlock::BulletedListItem {
+        bulleted_list_item: text_and_children(text),
+    }
+}
+
+pub fn numbered_list_item<T: IntoRichText>(text: T) -> NewBlock {
+    NewBlock::NumberedListItem {
+        numbered_list_item: text_and_children(text),
+    }
+}
+
+pub fn toggle<T: IntoRichText>(text: T) -> NewBlock {
+    NewBlock::Toggle {
+        toggle: text_and_children(text),
+    }
+}
+
+pub fn todo<T: IntoRichText>(checked: bool, text: T) -> NewBlock {
+    NewBlock::ToDo {
+        to_do: NewToDo {
+            rich_text: text.into_rich_text(),
+            checked,
+            ..Default::default()
+        },
+    }
+}
+
+pub fn code<T: Into<String>>(language: CodeLanguage, source: T) -> NewBlock {
+    NewBlock::Code {
+        code: CodeFields {
+            rich_text: source.into().into_rich_text(),
+            caption: vec![],
+            language,
+        },
+    }
+}
+
+/// callout with an emoji icon such as `"💡"`
+pub fn callout<E: Into<String>, T: IntoRichText>(emoji: E, text: T) -> NewBlock {
+    NewBlock::CallOut {
+        call_out: NewCallOut {
+            rich_text: text.into_rich_text(),
+            icon: Some(FileOrEmojiObject::Emoji {
+                emoji: emoji.into(),
+            }),
+            ..Default::default()
+        },
+    }
+}
+
+/// block equation written in KaTeX
+pub fn equation<T: Into<String>>(expression: T) -> NewBlock {
+    NewBlock::Equation {
+        equation: Equation {
+            expression: expression.into(),
+        },
+    }
+}
+
+pub fn divider() -> NewBlock {
+    NewBlock::Divider {
+        divider: EmptyObject {},
+    }
+}
+
+pub fn breadcrumb() -> NewBlock {
+    NewBlock::Breadcrumb {
+        breadcrumb: EmptyObject {},
+    }
+}
+
+pub fn table_of_contents() -> NewBlock {
+    NewBlock::TableOfContents {
+        table_of_contents: TableOfContents {
+            color: TextColor::Default,
+        },
+    }
+}
+
+pub fn bookmark<T: Into<String>>(url: T) -> NewBlock {
+    NewBlock::Bookmark {
+        bookmark: BookmarkFields {
+            url: url.into(),
+            caption: vec![],
+        },
+    }
+}
+
+pub fn embed<T: Into<String>>(url: T) -> NewBlock {
+    NewBlock::Embed {
+        embed: EmbedFields { url: url.into() },
+    }
+}
+
+/// image hosted at an external url
+pub fn image<T: Into<String>>(url: T) -> NewBlock {
+    NewBlock::Image {
+        image: external(url),
+    }
+}
+
+/// video hosted at an external url
+pub fn video<T: Into<String>>(url: T) -> NewBlock {
+    NewBlock::Video {
+        video: external(url),
+    }
+}
+
+/// pdf hosted at an external url
+pub fn pdf<T: Into<String>>(url: T) -> NewBlock {
+    NewBlock::Pdf { pdf: external(url) }
+}
+
+/// file hosted at an external url
+pub fn file<T: Into<String>>(url: T) -> NewBlock {
+    NewBlock::File {
+        file: external(url),
+    }
+}
+
+/// one column per inner vector, Notion needs at least two
+pub fn column_list(columns: Vec<Vec<NewBlock>>) -> NewBlock {
+    NewBlock::ColumnList {
+        column_list: NewColumnList {
+            children: columns
+                .into_iter()
+                .map(|children| NewBlock::Column {
+                    column: NewColumn { children },
+                })
+                .collect(),
+        },
+    }
+}
+
+pub fn table_row<T: IntoRichText>(cells: Vec<T>) -> NewBlock {
+    NewBlock::TableRow {
+        table_row: TableRowFields {
+            cells: cells
+                .into_iter()
+                .map(IntoRichText::into_rich_text)
+                .collect(),
+        },
+    }
+}
+
+/// table of [`table_row`]s, as wide as its widest row, shorter rows get empty cells
+pub fn table(mut rows: Vec<NewBlock>) -> NewBlock {
+    let table_width = rows
+        .iter()
+        .map(|row| match row {
+            NewBlock::TableRow { table_row } => table_row.cells.len() as u64,
+            _ => 0,
+        })
+        .max()
+        .unwrap_or_default();
+    for row in &mut rows {
+        if let NewBlock::TableRow { table_row } = row {
+            table_row.cells.resize(table_width as usize, vec![]);
+        }
+    }
+    NewBlock::Table {
+        table: NewTable {
+            table_width,
+            has_column_header: false,
+            has_row_header: false,
+            children: rows,
+        },
+    }
+}
+
+impl NewBlock {
+    fn rich_text_mut(&mut self) -> Option<&mut Vec<RichText>> {
+        use NewBlock::*;
+        match self {
+            Paragraph { paragraph: text }
+            | Quote { quote: text }
+            | BulletedListItem {
+                bulleted_list_item: text,
+            }
+            | NumberedListItem {
+                numbered_list_item: text,
+            }
+            | Toggle { toggle: text } => Some(&mut text.rich_text),
+            Heading1 { heading_1: heading }
+            | Heading2 { heading_2: heading }
+            | Heading3 { heading_3: heading } => Some(&mut heading.rich_text),
+            CallOut { call_out } => Some(&mut call_out.rich_text),
+            ToDo { to_do } => Some(&mut to_do.rich_text),
+            Code { code } => Some(&mut code.rich_text),
+            Template { template } => Some(&mut template.rich_text),
+            _ => None,
+        }
+    }
+    /// apply `f` to every span of the block's text
+    pub fn map_rich_text<F: FnMut(RichText) -> RichText>(mut self, f: F) -> Self {
+        if let Some(rich_text) = self.rich_text_mut() {
+            *rich_text = std::mem::take(rich_text).into_iter().map(f).collect();
+        }
+        self
+    }
+    pub fn bold(self) -> Self {
+        self.map_rich_text(RichText::bold)
+    }
+    pub fn italic(self) -> Self {
+        self.map_rich_text(RichText::italic)
+    }
+    pub fn strikethrough(self) -> Self {
+        self.map_rich_text(RichText::strikethrough)
+    }
+    pub fn underline(self) -> Self {
+        self.map_rich_text(RichText::underline)
+    }
+    /// color of the whole block, ignored by blocks without one
+    pub fn color(mut self, color: TextColor) -> Self {
+        use NewBlock::*;
+        match &mut self {
+            Paragraph { paragraph: text }
+            | Quote { quote: text }
+            | BulletedListItem {
+                bulleted_list_item: text,
+            }
+            | NumberedListItem {
+                numbered_list_item: text,
+            }
+            | Toggle { toggle: text } => text.color = color,
+            Heading1 { heading_1: heading }
+            | Heading2 { heading_2: heading }
+            | Heading3 { heading_3: heading } => heading.color = color,
+            CallOut { call_out } => call_out.color = color,
+            ToDo { to_do } => to_do.color = color,
+            TableOfContents { table_of_contents } => table_of_contents.color = color,
+            _ => {}
+        }
+        self
+    }
+    /// nest blocks below this one, headings become toggle headings,
+    /// ignored by blocks which can not hold children
+    pub fn children(mut self, children: Vec<NewBlock>) -> Self {
+        use NewBlock::*;
+        match &mut self {
+            Paragraph { paragraph: text }
+            | Quote { quote: text }
+            | BulletedListItem {
+                bulleted_list_item: text,
+            }
+            | NumberedListItem {
+                numbered_list_item: text,
+            }
+            | Toggle { toggle: text } => text.children = Some(children),
+            Heading1 { heading_1: heading }
+            | Heading2 { heading_2: heading }
+            | Heading3 { heading_3: heading } => {
+                heading.is_toggleable = true;
+                heading.children = Some(children);
+            }
+            CallOut { call_out } => call_out.children = Some(children),
+            ToDo { to_do } => to_do.children = Some(children),
+            SyncedBlock { synced_block } => synced_block.children = Some(children),
+            ColumnList { column_list } => column_list.children = children,
+            Column { column } => column.children = children,
+            Template { template } => template.children = children,
+            Table { table } => table.children = children,
+            _ => {}
+        }
+        self
+    }
+    /// caption of code and bookmark blocks
+    pub fn caption<T: IntoRichText>(mut self, caption: T) -> Self {
+        match &mut self {
+            NewBlock::Code { code } => code.caption = caption.into_rich_text(),
+            NewBlock::Bookmark { bookmark } => bookmark.caption = caption.into_rich_text(),
+            _ => {}
+        }
+        self
+    }
+    /// use the first row of a table as its header
+    pub fn column_header(mut self) -> Self {
+        if let NewBlock::Table { table } = &mut self {
+            table.has_column_header = true;
+        }
+        self
+    }
+    /// use the first column of a table as its header
+    pub fn row_header(mut self) -> Self {
+        if let NewBlock::Table { table } = &mut self {
+            table.has_row_header = true;
+        }
+        self
+    }
+}
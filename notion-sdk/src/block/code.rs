@@ -1,91 +1,7 @@
+use crate::block::CodeLanguage;
 use crate::common::rich_text::RichText;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
-#[serde(rename_all = "lowercase")]
-pub enum CodeLanguage {
-    Abap,
-    Arduino,
-    Bash,
-    Basic,
-    C,
-    Clojure,
-    Coffeescript,
-    #[serde(rename = "c++")]
-    CPlusPlus,
-    #[serde(rename = "c#")]
-    CSharp,
-    Css,
-    Dart,
-    Diff,
-    Docker,
-    Elixir,
-    Elm,
-    Erlang,
-    Flow,
-    Fortran,
-    #[serde(rename = "f#")]
-    FSharp,
-    Gherkin,
-    Glsl,
-    Go,
-    Graphql,
-    Groovy,
-    Haskell,
-    Html,
-    Java,
-    Javascript,
-    Json,
-    Julia,
-    Kotlin,
-    Latex,
-    Less,
-    Lisp,
-    Livescript,
-    Lua,
-    Makefile,
-    Markdown,
-    Markup,
-    Matlab,
-    Mermaid,
-    Nix,
-    #[serde(rename = "objective-c")]
-    ObjectiveC,
-    Ocaml,
-    Pascal,
-    Perl,
-    Php,
-    #[serde(rename = "plain text")]
-    PlainText,
-    Powershell,
-    Prolog,
-    Protobuf,
-    Python,
-    R,
-    Reason,
-    Ruby,
-    Rust,
-    Sass,
-    Scala,
-    Scheme,
-    Scss,
-    Shell,
-    Sql,
-    Swift,
-    Typescript,
-    #[serde(rename = "vb.net")]
-    VbNet,
-    Verilog,
-    Vhdl,
-    #[serde(rename = "visual basic")]
-    VisualBasic,
-    Webassembly,
-    Xml,
-    Yaml,
-    #[serde(rename = "java/c/c++/c#")]
-    JavaCAndCPlusPlusAndCSharp,
-}
-
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
 pub struct CodeFields {
     pub rich_text: Vec<RichText>,
@@ -1,3 +1,4 @@
+use crate::pages::id::PageId;
 use crate::Error;
 use std::fmt::Display;
 
@@ -21,3 +22,10 @@ impl std::str::FromStr for BlockId {
         }
     }
 }
+
+/// Pages are blocks too, a page id can be used wherever a block id is expected.
+impl From<PageId> for BlockId {
+    fn from(id: PageId) -> Self {
+        BlockId(id.0)
+    }
+}
@@ -11,14 +11,20 @@
 //!     color: TextColor::Default
 //! };
 //! ```
+mod aka;
 mod api;
+pub mod builder;
 mod code;
 pub mod id;
+mod new;
 mod todo;
+mod update;
 
-use crate::block::code::CodeFields;
+pub use crate::block::code::CodeFields;
 use crate::block::id::BlockId;
-use crate::block::todo::ToDoFields;
+pub use crate::block::new::*;
+pub use crate::block::todo::ToDoFields;
+pub use crate::block::update::*;
 use crate::common::file::{FileObject, FileOrEmojiObject};
 use crate::common::rich_text::{RichText, Text, TextColor};
 use crate::database::id::DatabaseId;
@@ -40,19 +46,19 @@ pub enum Block {
     Heading1 {
         #[serde(flatten)]
         common: BlockCommon,
-        heading_1: Text,
+        heading_1: HeadingFields,
     },
     #[serde(rename = "heading_2")]
     Heading2 {
         #[serde(flatten)]
         common: BlockCommon,
-        heading_2: Text,
+        heading_2: HeadingFields,
     },
     #[serde(rename = "heading_3")]
     Heading3 {
         #[serde(flatten)]
         common: BlockCommon,
-        heading_3: Text,
+        heading_3: HeadingFields,
     },
     #[serde(rename = "callout")]
     CallOut {
@@ -316,11 +322,15 @@ pub struct TableOfContents {
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
 pub struct ColumnListFields {
+    /// Not returned by Notion, filled by [`NotionApi::fetch_block_tree`](crate::NotionApi::fetch_block_tree).
+    #[serde(default)]
     pub children: Vec<Block>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
 pub struct ColumnFields {
+    /// Not returned by Notion, filled by [`NotionApi::fetch_block_tree`](crate::NotionApi::fetch_block_tree).
+    #[serde(default)]
     pub children: Vec<Block>,
 }
 
@@ -332,6 +342,8 @@ pub struct LinkPreviewFields {
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
 pub struct TemplateFields {
     pub rich_text: Vec<RichText>,
+    /// Not returned by Notion, filled by [`NotionApi::fetch_block_tree`](crate::NotionApi::fetch_block_tree).
+    #[serde(default)]
     pub children: Vec<Block>,
 }
 
@@ -351,6 +363,8 @@ pub struct SyncedFromObject {
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
 pub struct SyncedBlockFields {
     pub synced_from: Option<SyncedFromObject>,
+    /// Not returned by Notion, filled by [`NotionApi::fetch_block_tree`](crate::NotionApi::fetch_block_tree).
+    #[serde(default)]
     pub children: Vec<Block>,
 }
 
@@ -359,12 +373,15 @@ pub struct TableFields {
     pub table_width: u64,
     pub has_column_header: bool,
     pub has_row_header: bool,
+    /// Not returned by Notion, filled by [`NotionApi::fetch_block_tree`](crate::NotionApi::fetch_block_tree).
+    #[serde(default)]
     pub children: Vec<Block>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
 pub struct TableRowFields {
-    pub cells: Vec<RichText>,
+    /// One rich text array per cell.
+    pub cells: Vec<Vec<RichText>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
@@ -384,21 +401,50 @@ pub struct TextAndChildren {
     pub color: TextColor,
 }
 
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct HeadingFields {
+    pub rich_text: Vec<RichText>,
+    pub color: TextColor,
+    /// Toggle headings can hold children.
+    #[serde(default)]
+    pub is_toggleable: bool,
+    pub children: Option<Vec<Block>>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
 pub struct CallOut {
     pub rich_text: Vec<RichText>,
     pub icon: FileOrEmojiObject,
+    pub children: Option<Vec<Block>>,
     pub color: TextColor,
 }
 
-#[derive(Serialize, Debug, Eq, PartialEq)]
-pub struct UpdateBlock {
-    pub paragraph: Option<TextAndChildren>,
-    pub archived: bool,
+/// How [`NotionApi::fetch_block_tree`](crate::NotionApi::fetch_block_tree) walks a page.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct BlockTreeOptions {
+    /// Levels of descendants to fetch, `Some(1)` only fetches the direct children.
+    pub max_depth: Option<usize>,
+    /// Blocks whose children are fetched at the same time.
+    pub concurrency: usize,
+}
+
+impl Default for BlockTreeOptions {
+    fn default() -> Self {
+        BlockTreeOptions {
+            max_depth: None,
+            concurrency: 3,
+        }
+    }
 }
 
+/// Notion accepts at most this many children in one append request.
+pub const MAX_APPEND_CHILDREN: usize = 100;
+
+/// <https://developers.notion.com/reference/patch-block-children>
 #[derive(Serialize, Debug, Eq, PartialEq)]
 pub struct CreateBlock {
-    pub archived: bool,
-    pub children: Vec<Block>,
+    pub children: Vec<NewBlock>,
+    /// Insert the children after this block instead of at the end.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<BlockId>,
 }
@@ -0,0 +1,478 @@
+use crate::block::{
+    Block, BookmarkFields, CallOut, CodeFields, ColumnFields, ColumnListFields, EmbedFields,
+    Equation, HeadingFields, LinkToPageFields, SyncedBlockFields, SyncedFromObject, TableFields,
+    TableOfContents, TableRowFields, TemplateFields, TextAndChildren, ToDoFields,
+};
+use crate::common::file::{FileObject, FileOrEmojiObject};
+use crate::common::rich_text::{RichText, TextColor};
+use crate::Error;
+use serde::{Deserialize, Serialize};
+
+/// A block as sent to Notion when creating content.
+///
+/// Unlike [`Block`] it carries no id, timestamps or authors, which only exist once Notion stored it.
+/// A fetched block can be turned into a new one with [`NewBlock::try_from`] to copy it elsewhere.
+/// See <https://developers.notion.com/reference/block>
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+#[serde(tag = "type")]
+#[serde(rename_all = "snake_case")]
+pub enum NewBlock {
+    Paragraph {
+        paragraph: NewTextAndChildren,
+    },
+    #[serde(rename = "heading_1")]
+    Heading1 {
+        heading_1: NewHeading,
+    },
+    #[serde(rename = "heading_2")]
+    Heading2 {
+        heading_2: NewHeading,
+    },
+    #[serde(rename = "heading_3")]
+    Heading3 {
+        heading_3: NewHeading,
+    },
+    #[serde(rename = "callout")]
+    CallOut {
+        #[serde(rename = "callout")]
+        call_out: NewCallOut,
+    },
+    Quote {
+        quote: NewTextAndChildren,
+    },
+    BulletedListItem {
+        bulleted_list_item: NewTextAndChildren,
+    },
+    NumberedListItem {
+        numbered_list_item: NewTextAndChildren,
+    },
+    ToDo {
+        to_do: NewToDo,
+    },
+    Toggle {
+        toggle: NewTextAndChildren,
+    },
+    Code {
+        code: CodeFields,
+    },
+    Embed {
+        embed: EmbedFields,
+    },
+    Image {
+        image: FileObject,
+    },
+    Video {
+        video: FileObject,
+    },
+    File {
+        file: FileObject,
+    },
+    Pdf {
+        pdf: FileObject,
+    },
+    Bookmark {
+        bookmark: BookmarkFields,
+    },
+    Equation {
+        equation: Equation,
+    },
+    Divider {
+        #[serde(default)]
+        divider: EmptyObject,
+    },
+    TableOfContents {
+        table_of_contents: TableOfContents,
+    },
+    Breadcrumb {
+        #[serde(default)]
+        breadcrumb: EmptyObject,
+    },
+    ColumnList {
+        column_list: NewColumnList,
+    },
+    Column {
+        column: NewColumn,
+    },
+    Template {
+        template: NewTemplate,
+    },
+    LinkToPage {
+        link_to_page: LinkToPageFields,
+    },
+    Table {
+        table: NewTable,
+    },
+    SyncedBlock {
+        synced_block: NewSyncedBlock,
+    },
+    TableRow {
+        table_row: TableRowFields,
+    },
+}
+
+/// Serialized as `{}`, the payload of blocks without any content such as a divider.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Copy, Default)]
+pub struct EmptyObject {}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+pub struct NewTextAndChildren {
+    pub rich_text: Vec<RichText>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<NewBlock>>,
+    #[serde(default)]
+    pub color: TextColor,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+pub struct NewHeading {
+    pub rich_text: Vec<RichText>,
+    #[serde(default)]
+    pub color: TextColor,
+    #[serde(default)]
+    pub is_toggleable: bool,
+    /// Only allowed on toggleable headings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<NewBlock>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+pub struct NewToDo {
+    pub rich_text: Vec<RichText>,
+    #[serde(default)]
+    pub checked: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<NewBlock>>,
+    #[serde(default)]
+    pub color: TextColor,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+pub struct NewCallOut {
+    pub rich_text: Vec<RichText>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<FileOrEmojiObject>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<NewBlock>>,
+    #[serde(default)]
+    pub color: TextColor,
+}
+
+/// Must contain at least two [`NewBlock::Column`].
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+pub struct NewColumnList {
+    pub children: Vec<NewBlock>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+pub struct NewColumn {
+    pub children: Vec<NewBlock>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+pub struct NewTemplate {
+    pub rich_text: Vec<RichText>,
+    pub children: Vec<NewBlock>,
+}
+
+/// `children` holds the [`NewBlock::TableRow`]s, each one with `table_width` cells.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+pub struct NewTable {
+    pub table_width: u64,
+    pub has_column_header: bool,
+    pub has_row_header: bool,
+    pub children: Vec<NewBlock>,
+}
+
+/// An original synced block has no `synced_from` and holds its children,
+/// a duplicate only refers to the original.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+pub struct NewSyncedBlock {
+    pub synced_from: Option<SyncedFromObject>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<NewBlock>>,
+}
+
+impl NewBlock {
+    /// the children nested below this block, `None` for blocks which can not hold children
+    pub(crate) fn children_mut(&mut self) -> Option<&mut Vec<NewBlock>> {
+        use NewBlock::*;
+        match self {
+            Paragraph { paragraph: text }
+            | Quote { quote: text }
+            | BulletedListItem {
+                bulleted_list_item: text,
+            }
+            | NumberedListItem {
+                numbered_list_item: text,
+            }
+            | Toggle { toggle: text } => text.children.as_mut(),
+            Heading1 { heading_1: heading }
+            | Heading2 { heading_2: heading }
+            | Heading3 { heading_3: heading } => heading.children.as_mut(),
+            CallOut { call_out } => call_out.children.as_mut(),
+            ToDo { to_do } => to_do.children.as_mut(),
+            SyncedBlock { synced_block } => synced_block.children.as_mut(),
+            ColumnList { column_list } => Some(&mut column_list.children),
+            Column { column } => Some(&mut column.children),
+            Template { template } => Some(&mut template.children),
+            Table { table } => Some(&mut table.children),
+            _ => None,
+        }
+    }
+    /// remove the children nested below this block
+    pub(crate) fn take_children(&mut self) -> Vec<NewBlock> {
+        use NewBlock::*;
+        let children = match self {
+            Paragraph { paragraph: text }
+            | Quote { quote: text }
+            | BulletedListItem {
+                bulleted_list_item: text,
+            }
+            | NumberedListItem {
+                numbered_list_item: text,
+            }
+            | Toggle { toggle: text } => text.children.take(),
+            Heading1 { heading_1: heading }
+            | Heading2 { heading_2: heading }
+            | Heading3 { heading_3: heading } => heading.children.take(),
+            CallOut { call_out } => call_out.children.take(),
+            ToDo { to_do } => to_do.children.take(),
+            SyncedBlock { synced_block } => synced_block.children.take(),
+            block => block.children_mut().map(std::mem::take),
+        };
+        children.unwrap_or_default()
+    }
+}
+
+fn new_children(children: Vec<Block>) -> Result<Vec<NewBlock>, Error> {
+    children.into_iter().map(NewBlock::try_from).collect()
+}
+
+fn new_optional_children(children: Option<Vec<Block>>) -> Result<Option<Vec<NewBlock>>, Error> {
+    children.map(new_children).transpose()
+}
+
+impl TryFrom<TextAndChildren> for NewTextAndChildren {
+    type Error = Error;
+
+    fn try_from(text: TextAndChildren) -> Result<Self, Self::Error> {
+        Ok(NewTextAndChildren {
+            rich_text: text.rich_text,
+            children: new_optional_children(text.children)?,
+            color: text.color,
+        })
+    }
+}
+
+impl TryFrom<HeadingFields> for NewHeading {
+    type Error = Error;
+
+    fn try_from(heading: HeadingFields) -> Result<Self, Self::Error> {
+        Ok(NewHeading {
+            rich_text: heading.rich_text,
+            color: heading.color,
+            is_toggleable: heading.is_toggleable,
+            children: new_optional_children(heading.children)?,
+        })
+    }
+}
+
+impl TryFrom<ToDoFields> for NewToDo {
+    type Error = Error;
+
+    fn try_from(to_do: ToDoFields) -> Result<Self, Self::Error> {
+        Ok(NewToDo {
+            rich_text: to_do.rich_text,
+            checked: to_do.checked,
+            children: new_optional_children(to_do.children)?,
+            color: to_do.color,
+        })
+    }
+}
+
+impl TryFrom<CallOut> for NewCallOut {
+    type Error = Error;
+
+    fn try_from(call_out: CallOut) -> Result<Self, Self::Error> {
+        if let FileOrEmojiObject::File { .. } = call_out.icon {
+            return Err(Error::HostedFile {
+                block_type: "callout",
+            });
+        }
+        Ok(NewCallOut {
+            rich_text: call_out.rich_text,
+            icon: Some(call_out.icon),
+            children: new_optional_children(call_out.children)?,
+            color: call_out.color,
+        })
+    }
+}
+
+impl TryFrom<ColumnListFields> for NewColumnList {
+    type Error = Error;
+
+    fn try_from(column_list: ColumnListFields) -> Result<Self, Self::Error> {
+        Ok(NewColumnList {
+            children: new_children(column_list.children)?,
+        })
+    }
+}
+
+impl TryFrom<ColumnFields> for NewColumn {
+    type Error = Error;
+
+    fn try_from(column: ColumnFields) -> Result<Self, Self::Error> {
+        Ok(NewColumn {
+            children: new_children(column.children)?,
+        })
+    }
+}
+
+impl TryFrom<TemplateFields> for NewTemplate {
+    type Error = Error;
+
+    fn try_from(template: TemplateFields) -> Result<Self, Self::Error> {
+        Ok(NewTemplate {
+            rich_text: template.rich_text,
+            children: new_children(template.children)?,
+        })
+    }
+}
+
+impl TryFrom<TableFields> for NewTable {
+    type Error = Error;
+
+    fn try_from(table: TableFields) -> Result<Self, Self::Error> {
+        Ok(NewTable {
+            table_width: table.table_width,
+            has_column_header: table.has_column_header,
+            has_row_header: table.has_row_header,
+            children: new_children(table.children)?,
+        })
+    }
+}
+
+impl TryFrom<SyncedBlockFields> for NewSyncedBlock {
+    type Error = Error;
+
+    fn try_from(synced_block: SyncedBlockFields) -> Result<Self, Self::Error> {
+        let children = match synced_block.synced_from {
+            // a duplicate must not repeat the content of its original
+            Some(_) => None,
+            None => Some(new_children(synced_block.children)?),
+        };
+        Ok(NewSyncedBlock {
+            synced_from: synced_block.synced_from,
+            children,
+        })
+    }
+}
+
+/// Notion-hosted files come with an expiring url and can not be sent back,
+/// only external files can be copied.
+fn external_file(file: FileObject, block_type: &'static str) -> Result<FileObject, Error> {
+    match file {
+        FileObject::External { .. } => Ok(file),
+        FileObject::File { .. } => Err(Error::HostedFile { block_type }),
+    }
+}
+
+/// Copy a fetched block, including whatever children it was fetched with.
+///
+/// Child pages, child databases, link previews and unsupported blocks can not be created
+/// through the API and fail with [`Error::UnsupportedBlock`].
+/// Images, videos, files, pdfs and callout icons uploaded to Notion fail with [`Error::HostedFile`].
+impl TryFrom<Block> for NewBlock {
+    type Error = Error;
+
+    fn try_from(block: Block) -> Result<Self, Self::Error> {
+        let new_block = match block {
+            Block::Paragraph { paragraph, .. } => NewBlock::Paragraph {
+                paragraph: paragraph.try_into()?,
+            },
+            Block::Heading1 { heading_1, .. } => NewBlock::Heading1 {
+                heading_1: heading_1.try_into()?,
+            },
+            Block::Heading2 { heading_2, .. } => NewBlock::Heading2 {
+                heading_2: heading_2.try_into()?,
+            },
+            Block::Heading3 { heading_3, .. } => NewBlock::Heading3 {
+                heading_3: heading_3.try_into()?,
+            },
+            Block::CallOut { call_out, .. } => NewBlock::CallOut {
+                call_out: call_out.try_into()?,
+            },
+            Block::Quote { quote, .. } => NewBlock::Quote {
+                quote: quote.try_into()?,
+            },
+            Block::BulletedListItem {
+                bulleted_list_item, ..
+            } => NewBlock::BulletedListItem {
+                bulleted_list_item: bulleted_list_item.try_into()?,
+            },
+            Block::NumberedListItem {
+                numbered_list_item, ..
+            } => NewBlock::NumberedListItem {
+                numbered_list_item: numbered_list_item.try_into()?,
+            },
+            Block::ToDo { to_do, .. } => NewBlock::ToDo {
+                to_do: to_do.try_into()?,
+            },
+            Block::Toggle { toggle, .. } => NewBlock::Toggle {
+                toggle: toggle.try_into()?,
+            },
+            Block::Code { code, .. } => NewBlock::Code { code },
+            Block::Embed { embed, .. } => NewBlock::Embed { embed },
+            Block::Image { image, .. } => NewBlock::Image {
+                image: external_file(image, "image")?,
+            },
+            Block::Video { video, .. } => NewBlock::Video {
+                video: external_file(video, "video")?,
+            },
+            Block::File { file, .. } => NewBlock::File {
+                file: external_file(file, "file")?,
+            },
+            Block::Pdf { pdf, .. } => NewBlock::Pdf {
+                pdf: external_file(pdf, "pdf")?,
+            },
+            Block::Bookmark { bookmark, .. } => NewBlock::Bookmark { bookmark },
+            Block::Equation { equation, .. } => NewBlock::Equation { equation },
+            Block::Divider { .. } => NewBlock::Divider {
+                divider: EmptyObject {},
+            },
+            Block::TableOfContents {
+                table_of_contents, ..
+            } => NewBlock::TableOfContents { table_of_contents },
+            Block::Breadcrumb { .. } => NewBlock::Breadcrumb {
+                breadcrumb: EmptyObject {},
+            },
+            Block::ColumnList { column_list, .. } => NewBlock::ColumnList {
+                column_list: column_list.try_into()?,
+            },
+            Block::Column { column, .. } => NewBlock::Column {
+                column: column.try_into()?,
+            },
+            Block::Template { template, .. } => NewBlock::Template {
+                template: template.try_into()?,
+            },
+            Block::LinkToPage { link_to_page, .. } => NewBlock::LinkToPage { link_to_page },
+            Block::Table { table, .. } => NewBlock::Table {
+                table: table.try_into()?,
+            },
+            Block::SyncedBlock { synced_block, .. } => NewBlock::SyncedBlock {
+                synced_block: synced_block.try_into()?,
+            },
+            Block::TableRow { table_row, .. } => NewBlock::TableRow { table_row },
+            block @ (Block::ChildPage { .. }
+            | Block::ChildDatabase { .. }
+            | Block::LinkPreview { .. }
+            | Block::Unsupported { .. }
+            | Block::Unknown) => {
+                return Err(Error::UnsupportedBlock {
+                    block_type: block.block_type(),
+                })
+            }
+        };
+        Ok(new_block)
+    }
+}
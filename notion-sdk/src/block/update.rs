@@ -0,0 +1,170 @@
+use crate::block::{CodeLanguage, Equation, LinkToPageFields, TableOfContents, TableRowFields};
+use crate::common::file::{FileObject, FileOrEmojiObject};
+use crate::common::rich_text::{RichText, TextColor};
+use serde::Serialize;
+
+/// Body of a block update, fields left as `None` keep their current value.
+///
+/// ## Examples
+/// ```rust
+/// use notion_sdk::block::{UpdateBlock, UpdateBlockFields, UpdateToDo};
+/// let update: UpdateBlock = UpdateBlockFields::ToDo(UpdateToDo {
+///     checked: Some(true),
+///     ..Default::default()
+/// })
+/// .into();
+/// assert_eq!(
+///     serde_json::to_string(&update).unwrap(),
+///     r#"{"to_do":{"checked":true}}"#
+/// );
+/// ```
+/// See <https://developers.notion.com/reference/update-a-block>
+#[derive(Serialize, Debug, Eq, PartialEq, Clone, Default)]
+pub struct UpdateBlock {
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub block: Option<UpdateBlockFields>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived: Option<bool>,
+}
+
+impl UpdateBlock {
+    /// move the block to the trash
+    pub fn archive() -> Self {
+        UpdateBlock {
+            block: None,
+            archived: Some(true),
+        }
+    }
+    /// restore an archived block
+    pub fn restore() -> Self {
+        UpdateBlock {
+            block: None,
+            archived: Some(false),
+        }
+    }
+}
+
+impl From<UpdateBlockFields> for UpdateBlock {
+    fn from(block: UpdateBlockFields) -> Self {
+        UpdateBlock {
+            block: Some(block),
+            archived: None,
+        }
+    }
+}
+
+/// The type specific part of a block update, which must match the type of the block.
+#[derive(Serialize, Debug, Eq, PartialEq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateBlockFields {
+    Paragraph(UpdateText),
+    #[serde(rename = "heading_1")]
+    Heading1(UpdateHeading),
+    #[serde(rename = "heading_2")]
+    Heading2(UpdateHeading),
+    #[serde(rename = "heading_3")]
+    Heading3(UpdateHeading),
+    #[serde(rename = "callout")]
+    CallOut(UpdateCallOut),
+    Quote(UpdateText),
+    BulletedListItem(UpdateText),
+    NumberedListItem(UpdateText),
+    ToDo(UpdateToDo),
+    Toggle(UpdateText),
+    Code(UpdateCode),
+    Embed(UpdateUrl),
+    /// Only external files can be set.
+    Image(FileObject),
+    /// Only external files can be set.
+    Video(FileObject),
+    /// Only external files can be set.
+    File(FileObject),
+    /// Only external files can be set.
+    Pdf(FileObject),
+    Bookmark(UpdateBookmark),
+    Equation(Equation),
+    TableOfContents(TableOfContents),
+    Template(UpdateTemplate),
+    LinkToPage(LinkToPageFields),
+    Table(UpdateTable),
+    TableRow(TableRowFields),
+}
+
+/// Paragraph, quote, list item and toggle updates.
+#[derive(Serialize, Debug, Eq, PartialEq, Clone, Default)]
+pub struct UpdateText {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rich_text: Option<Vec<RichText>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<TextColor>,
+}
+
+/// Templates have no color.
+#[derive(Serialize, Debug, Eq, PartialEq, Clone, Default)]
+pub struct UpdateTemplate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rich_text: Option<Vec<RichText>>,
+}
+
+#[derive(Serialize, Debug, Eq, PartialEq, Clone, Default)]
+pub struct UpdateHeading {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rich_text: Option<Vec<RichText>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<TextColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_toggleable: Option<bool>,
+}
+
+#[derive(Serialize, Debug, Eq, PartialEq, Clone, Default)]
+pub struct UpdateToDo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rich_text: Option<Vec<RichText>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checked: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<TextColor>,
+}
+
+#[derive(Serialize, Debug, Eq, PartialEq, Clone, Default)]
+pub struct UpdateCallOut {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rich_text: Option<Vec<RichText>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<FileOrEmojiObject>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<TextColor>,
+}
+
+#[derive(Serialize, Debug, Eq, PartialEq, Clone, Default)]
+pub struct UpdateCode {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rich_text: Option<Vec<RichText>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub caption: Option<Vec<RichText>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<CodeLanguage>,
+}
+
+#[derive(Serialize, Debug, Eq, PartialEq, Clone, Default)]
+pub struct UpdateUrl {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+#[derive(Serialize, Debug, Eq, PartialEq, Clone, Default)]
+pub struct UpdateBookmark {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub caption: Option<Vec<RichText>>,
+}
+
+/// The width of a table can not be changed after creation.
+#[derive(Serialize, Debug, Eq, PartialEq, Clone, Default)]
+pub struct UpdateTable {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_column_header: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_row_header: Option<bool>,
+}
@@ -0,0 +1,61 @@
+use crate::block::id::BlockId;
+use crate::comment::{Comment, CreateComment};
+use crate::pagination::{paginate, FromObject, ListResponse, Paging};
+use crate::{Error, NotionApi, Object};
+use futures::Stream;
+
+const ROUTER: &str = "comments";
+
+impl NotionApi {
+    /// List the open comments of a page or block, at most 100 per request
+    pub async fn comments_list(
+        &self,
+        block_id: BlockId,
+        paging: Option<Paging>,
+    ) -> Result<ListResponse<Comment>, Error> {
+        self.raw_comments_list(block_id, paging)
+            .await
+            .and_then(FromObject::from_object)
+    }
+    /// Yield every open comment of a page or block, fetching more as the stream is polled
+    pub fn comments_list_stream(
+        &self,
+        block_id: BlockId,
+    ) -> impl Stream<Item = Result<Comment, Error>> + '_ {
+        paginate(move |start_cursor| {
+            let block_id = block_id.clone();
+            let paging = Paging {
+                start_cursor,
+                page_size: None,
+            };
+            async move { self.comments_list(block_id, Some(paging)).await }
+        })
+    }
+    /// Comment on a page or reply in a discussion
+    pub async fn comments_create(&self, comment: CreateComment) -> Result<Comment, Error> {
+        self.raw_comments_create(comment)
+            .await
+            .and_then(FromObject::from_object)
+    }
+}
+
+impl NotionApi {
+    pub async fn raw_comments_list(
+        &self,
+        block_id: BlockId,
+        paging: Option<Paging>,
+    ) -> Result<Object, Error> {
+        let u = format!("{}/{}", self.base_path, ROUTER);
+        self.request(
+            self.client
+                .get(u)
+                .query(&[("block_id", block_id.to_string())])
+                .query(&paging.unwrap_or_default()),
+        )
+        .await
+    }
+    pub async fn raw_comments_create(&self, comment: CreateComment) -> Result<Object, Error> {
+        let u = format!("{}/{}", self.base_path, ROUTER);
+        self.request(self.client.post(u).json(&comment)).await
+    }
+}
@@ -0,0 +1,46 @@
+use crate::error::Error;
+
+use std::fmt::Display;
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Eq, PartialEq, Hash, Clone)]
+#[serde(transparent)]
+pub struct CommentId(pub uuid::Uuid);
+
+impl Display for CommentId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::str::FromStr for CommentId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match uuid::Uuid::parse_str(s) {
+            Ok(i) => Ok(CommentId(i)),
+            Err(e) => Err(Error::UUID { source: e }),
+        }
+    }
+}
+
+/// Thread the comments of a page or block belong to.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Eq, PartialEq, Hash, Clone)]
+#[serde(transparent)]
+pub struct DiscussionId(pub uuid::Uuid);
+
+impl Display for DiscussionId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::str::FromStr for DiscussionId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match uuid::Uuid::parse_str(s) {
+            Ok(i) => Ok(DiscussionId(i)),
+            Err(e) => Err(Error::UUID { source: e }),
+        }
+    }
+}
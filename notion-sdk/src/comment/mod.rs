@@ -1,3 +1,89 @@
 //!
 //! # Notion Comment
 //!
+//! Comments are grouped in discussions: a comment on a page starts a new discussion,
+//! a reply joins an existing one.
+//!
+//! ## Examples
+//! ```rust,no_run
+//! use notion_sdk::comment::CreateComment;
+//! use notion_sdk::common::rich_text::RichText;
+//! use notion_sdk::NotionApi;
+//! # async fn run() -> Result<(), notion_sdk::error::Error> {
+//! let notion = NotionApi::new("token")?;
+//! let page_id: notion_sdk::pages::id::PageId = "3f8d5d7e-6c0a-4d9b-9a51-9f3c2b1e8a10".parse()?;
+//! let comment = notion
+//!     .comments_create(CreateComment::page(page_id.clone(), vec![RichText::text("Needs an example")]))
+//!     .await?;
+//! notion
+//!     .comments_create(comment.reply(vec![RichText::text("Added one")]))
+//!     .await?;
+//! let comments = notion.comments_list(page_id.into(), None).await?;
+//! # Ok(())
+//! # }
+//! ```
+mod api;
+pub mod id;
+
+use crate::comment::id::{CommentId, DiscussionId};
+use crate::common::parent::Parent;
+use crate::common::rich_text::RichText;
+use crate::pages::id::PageId;
+use crate::user::UserCommon;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct Comment {
+    pub id: CommentId,
+    /// Page or block the comment was made on.
+    pub parent: Parent,
+    pub discussion_id: DiscussionId,
+    pub created_time: DateTime<Utc>,
+    pub last_edited_time: DateTime<Utc>,
+    pub created_by: UserCommon,
+    pub rich_text: Vec<RichText>,
+}
+
+impl Comment {
+    /// Reply to the discussion of this comment.
+    pub fn reply(&self, rich_text: Vec<RichText>) -> CreateComment {
+        CreateComment::reply(self.discussion_id.clone(), rich_text)
+    }
+}
+
+/// Where a new comment goes.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+#[serde(untagged)]
+pub enum CommentTarget {
+    /// Start a new discussion on a page.
+    Page { parent: Parent },
+    /// Reply in an existing discussion.
+    Discussion { discussion_id: DiscussionId },
+}
+
+#[derive(Serialize, Debug, Eq, PartialEq)]
+pub struct CreateComment {
+    #[serde(flatten)]
+    pub target: CommentTarget,
+    pub rich_text: Vec<RichText>,
+}
+
+impl CreateComment {
+    /// Start a new discussion on a page.
+    pub fn page(page_id: PageId, rich_text: Vec<RichText>) -> Self {
+        CreateComment {
+            target: CommentTarget::Page {
+                parent: Parent::Page { page_id },
+            },
+            rich_text,
+        }
+    }
+    /// Reply in an existing discussion.
+    pub fn reply(discussion_id: DiscussionId, rich_text: Vec<RichText>) -> Self {
+        CreateComment {
+            target: CommentTarget::Discussion { discussion_id },
+            rich_text,
+        }
+    }
+}
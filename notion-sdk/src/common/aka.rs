@@ -23,6 +23,13 @@ impl FileOrEmojiObject {
 }
 
 impl FileObject {
+    /// where the file can be downloaded, internal files expire after an hour
+    pub fn url(&self) -> &str {
+        match self {
+            FileObject::File { file } => &file.url,
+            FileObject::External { external } => &external.url,
+        }
+    }
     pub fn external_file_from_url(url: Url) -> Self {
         FileObject::External {
             external: ExternalFileObject {
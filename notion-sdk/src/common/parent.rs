@@ -1,3 +1,4 @@
+use crate::block::id::BlockId;
 use crate::database::id::DatabaseId;
 use crate::pages::id::PageId;
 use serde::{Deserialize, Serialize};
@@ -14,5 +15,9 @@ pub enum Parent {
     Page {
         page_id: PageId,
     },
+    #[serde(rename = "block_id")]
+    Block {
+        block_id: BlockId,
+    },
     Workspace,
 }
@@ -1,3 +1,4 @@
+use crate::block::Equation;
 use crate::database::date::DateValue;
 use crate::database::Database;
 use crate::pages::Page;
@@ -8,6 +9,8 @@ use serde::{Deserialize, Serialize};
 /// A rich text object also contains annotations for style information.
 /// Arrays of rich text objects are used within property objects and property
 /// value objects to create what a user sees as a single text value in Notion.
+// mentions embed whole dates and users, boxing them would change the matched fields
+#[allow(clippy::large_enum_variant)]
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
 #[serde(tag = "type")]
 #[serde(rename_all = "snake_case")]
@@ -28,6 +31,7 @@ pub enum RichText {
     Equation {
         #[serde(flatten)]
         rich_text: RichTextCommon,
+        equation: Equation,
     },
 }
 
@@ -40,6 +44,173 @@ impl RichText {
             }
         }
     }
+    /// Properties common on all rich text objects
+    pub fn common(&self) -> &RichTextCommon {
+        use RichText::*;
+        match self {
+            Text { rich_text, .. } | Mention { rich_text, .. } | Equation { rich_text, .. } => {
+                rich_text
+            }
+        }
+    }
+    fn common_mut(&mut self) -> &mut RichTextCommon {
+        use RichText::*;
+        match self {
+            Text { rich_text, .. } | Mention { rich_text, .. } | Equation { rich_text, .. } => {
+                rich_text
+            }
+        }
+    }
+}
+
+/// Constructors for rich text sent to Notion
+///
+/// ## Examples
+/// ```rust
+/// use notion_sdk::common::rich_text::{RichText, TextColor};
+/// use notion_sdk::rich_text;
+/// let text = rich_text!["Read ", RichText::text("the docs").bold().link("https://developers.notion.com"), "!"];
+/// assert_eq!(text.len(), 3);
+/// ```
+impl RichText {
+    /// unformatted text
+    pub fn text<T: Into<String>>(content: T) -> Self {
+        let content = content.into();
+        RichText::Text {
+            rich_text: RichTextCommon {
+                plain_text: content.clone(),
+                href: None,
+                annotations: None,
+            },
+            text: Text {
+                content,
+                link: None,
+            },
+        }
+    }
+    /// inline equation written in KaTeX
+    pub fn equation<T: Into<String>>(expression: T) -> Self {
+        let expression = expression.into();
+        RichText::Equation {
+            rich_text: RichTextCommon {
+                plain_text: expression.clone(),
+                href: None,
+                annotations: None,
+            },
+            equation: Equation { expression },
+        }
+    }
+    /// change the annotations of this span
+    pub fn annotate<F: FnOnce(&mut Annotations)>(mut self, f: F) -> Self {
+        f(self
+            .common_mut()
+            .annotations
+            .get_or_insert_with(Default::default));
+        self
+    }
+    pub fn bold(self) -> Self {
+        self.annotate(|a| a.bold = Some(true))
+    }
+    pub fn italic(self) -> Self {
+        self.annotate(|a| a.italic = Some(true))
+    }
+    pub fn strikethrough(self) -> Self {
+        self.annotate(|a| a.strikethrough = Some(true))
+    }
+    pub fn underline(self) -> Self {
+        self.annotate(|a| a.underline = Some(true))
+    }
+    /// inline code
+    pub fn code(self) -> Self {
+        self.annotate(|a| a.code = Some(true))
+    }
+    pub fn color(self, color: TextColor) -> Self {
+        self.annotate(|a| a.color = Some(color))
+    }
+    /// turn a text span into a link, other spans are left unchanged
+    pub fn link<T: Into<String>>(mut self, url: T) -> Self {
+        if let RichText::Text { rich_text, text } = &mut self {
+            let url = url.into();
+            rich_text.href = Some(url.clone());
+            text.link = Some(Link { url });
+        }
+        self
+    }
+}
+
+/// Notion rejects text objects with more characters than this.
+pub const MAX_TEXT_LENGTH: usize = 2000;
+
+/// Split text spans longer than [`MAX_TEXT_LENGTH`] characters.
+pub(crate) fn split_long(spans: Vec<RichText>) -> Vec<RichText> {
+    spans
+        .into_iter()
+        .flat_map(|span| match span {
+            RichText::Text { rich_text, text }
+                if text.content.chars().count() > MAX_TEXT_LENGTH =>
+            {
+                let chars: Vec<char> = text.content.chars().collect();
+                chars
+                    .chunks(MAX_TEXT_LENGTH)
+                    .map(|chunk| {
+                        let content: String = chunk.iter().collect();
+                        RichText::Text {
+                            rich_text: RichTextCommon {
+                                plain_text: content.clone(),
+                                ..rich_text.clone()
+                            },
+                            text: Text {
+                                content,
+                                link: text.link.clone(),
+                            },
+                        }
+                    })
+                    .collect()
+            }
+            span => vec![span],
+        })
+        .collect()
+}
+
+/// Anything that can be used as the text of a block, see [`rich_text!`](crate::rich_text),
+/// text longer than [`MAX_TEXT_LENGTH`] is split into several spans
+pub trait IntoRichText {
+    fn into_rich_text(self) -> Vec<RichText>;
+}
+
+impl IntoRichText for &str {
+    fn into_rich_text(self) -> Vec<RichText> {
+        split_long(vec![RichText::text(self)])
+    }
+}
+
+impl IntoRichText for String {
+    fn into_rich_text(self) -> Vec<RichText> {
+        split_long(vec![RichText::text(self)])
+    }
+}
+
+impl IntoRichText for RichText {
+    fn into_rich_text(self) -> Vec<RichText> {
+        split_long(vec![self])
+    }
+}
+
+impl IntoRichText for Vec<RichText> {
+    fn into_rich_text(self) -> Vec<RichText> {
+        split_long(self)
+    }
+}
+
+/// Concatenate strings and [`RichText`] spans into a `Vec<RichText>`
+#[macro_export]
+macro_rules! rich_text {
+    ($($text:expr),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut rich_text = ::std::vec::Vec::new();
+        $(rich_text.extend($crate::common::rich_text::IntoRichText::into_rich_text($text));)*
+        rich_text
+    }};
 }
 
 /// Properties common on all rich text objects
@@ -55,13 +226,19 @@ pub struct RichTextCommon {
 
 /// Rich text annotations
 /// See <https://developers.notion.com/reference/rich-text#annotations>
-#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
 pub struct Annotations {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub bold: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub code: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub color: Option<TextColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub italic: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub strikethrough: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub underline: Option<bool>,
 }
 
@@ -95,9 +272,10 @@ pub enum MentionObject {
     Unknown,
 }
 
-#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Copy, Clone, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum TextColor {
+    #[default]
     Default,
     Gray,
     Brown,
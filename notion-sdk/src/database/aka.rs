@@ -1 +1,140 @@
+use crate::database::id::PropertyId;
+use crate::database::properties::{PropertyConfiguration, PropertyValue};
+use crate::database::select::SelectOption;
 
+impl PropertyValue {
+    /// type of the property as named by the Notion API
+    pub fn type_name(&self) -> &'static str {
+        use PropertyValue::*;
+        match self {
+            Title { .. } => "title",
+            Text { .. } => "rich_text",
+            Number { .. } => "number",
+            Select { .. } => "select",
+            Status { .. } => "status",
+            MultiSelect { .. } => "multi_select",
+            Date { .. } => "date",
+            Formula { .. } => "formula",
+            Relation { .. } => "relation",
+            Rollup { .. } => "rollup",
+            People { .. } => "people",
+            Files { .. } => "files",
+            Checkbox { .. } => "checkbox",
+            Url { .. } => "url",
+            Email { .. } => "email",
+            PhoneNumber { .. } => "phone_number",
+            CreatedTime { .. } => "created_time",
+            CreatedBy { .. } => "created_by",
+            LastEditedTime { .. } => "last_edited_time",
+            LastEditedBy { .. } => "last_edited_by",
+        }
+    }
+}
+
+impl PropertyConfiguration {
+    /// type of the property as named by the Notion API
+    pub fn type_name(&self) -> &'static str {
+        use PropertyConfiguration::*;
+        match self {
+            Title { .. } => "title",
+            Text { .. } => "rich_text",
+            Number { .. } => "number",
+            Select { .. } => "select",
+            Status { .. } => "status",
+            MultiSelect { .. } => "multi_select",
+            Date { .. } => "date",
+            People { .. } => "people",
+            Files { .. } => "files",
+            Checkbox { .. } => "checkbox",
+            Url { .. } => "url",
+            Email { .. } => "email",
+            PhoneNumber { .. } => "phone_number",
+            Formula { .. } => "formula",
+            Relation { .. } => "relation",
+            Rollup { .. } => "rollup",
+            CreatedTime { .. } => "created_time",
+            CreatedBy { .. } => "created_by",
+            LastEditedTime { .. } => "last_edited_time",
+            LastEditedBy { .. } => "last_edited_by",
+        }
+    }
+    pub fn id(&self) -> &PropertyId {
+        use PropertyConfiguration::*;
+        match self {
+            Title { id, .. }
+            | Text { id, .. }
+            | Number { id, .. }
+            | Select { id, .. }
+            | Status { id, .. }
+            | MultiSelect { id, .. }
+            | Date { id, .. }
+            | People { id, .. }
+            | Files { id, .. }
+            | Checkbox { id, .. }
+            | Url { id, .. }
+            | Email { id, .. }
+            | PhoneNumber { id, .. }
+            | Formula { id, .. }
+            | Relation { id, .. }
+            | Rollup { id, .. }
+            | CreatedTime { id, .. }
+            | CreatedBy { id, .. }
+            | LastEditedTime { id, .. }
+            | LastEditedBy { id, .. } => id,
+        }
+    }
+    /// name of the property as it appears in Notion
+    pub fn name(&self) -> &str {
+        use PropertyConfiguration::*;
+        match self {
+            Title { name, .. }
+            | Text { name, .. }
+            | Number { name, .. }
+            | Select { name, .. }
+            | Status { name, .. }
+            | MultiSelect { name, .. }
+            | Date { name, .. }
+            | People { name, .. }
+            | Files { name, .. }
+            | Checkbox { name, .. }
+            | Url { name, .. }
+            | Email { name, .. }
+            | PhoneNumber { name, .. }
+            | Formula { name, .. }
+            | Relation { name, .. }
+            | Rollup { name, .. }
+            | CreatedTime { name, .. }
+            | CreatedBy { name, .. }
+            | LastEditedTime { name, .. }
+            | LastEditedBy { name, .. } => name,
+        }
+    }
+    /// computed by Notion, pages can not be created or updated with a value for it,
+    /// files too as a [`FileReference`](crate::database::files::FileReference) can not be written back
+    pub fn is_read_only(&self) -> bool {
+        use PropertyConfiguration::*;
+        matches!(
+            self,
+            Files { .. }
+                | Formula { .. }
+                | Rollup { .. }
+                | CreatedTime { .. }
+                | CreatedBy { .. }
+                | LastEditedTime { .. }
+                | LastEditedBy { .. }
+        )
+    }
+    /// options of select, multi-select and status properties
+    pub fn options(&self) -> Option<&[SelectOption]> {
+        use PropertyConfiguration::*;
+        match self {
+            Select { select, .. }
+            | MultiSelect {
+                multi_select: select,
+                ..
+            } => Some(&select.options),
+            Status { status, .. } => Some(&status.options),
+            _ => None,
+        }
+    }
+}
@@ -1,17 +1,67 @@
 use crate::database::id::DatabaseId;
-use crate::database::{CreateDatabase, UpdateDatabase};
+use crate::database::{CreateDatabase, Database, UpdateDatabase};
+use crate::pages::Page;
+use crate::pagination::{paginate, FromObject, ListResponse, Pageable};
 use crate::search::DatabaseQuery;
 use crate::{Error, NotionApi, Object};
+use futures::Stream;
 
 const ROUTER: &str = "databases";
 
 impl NotionApi {
-    /// Retrieve a user
-    pub async fn databases_retrieve(&self, id: DatabaseId) -> Result<Object, Error> {
+    /// Retrieve a database
+    pub async fn databases_retrieve(&self, id: DatabaseId) -> Result<Database, Error> {
+        self.raw_databases_retrieve(id)
+            .await
+            .and_then(FromObject::from_object)
+    }
+    /// Query a database
+    pub async fn databases_query(
+        &self,
+        id: DatabaseId,
+        query: Option<DatabaseQuery>,
+    ) -> Result<ListResponse<Page>, Error> {
+        self.raw_databases_query(id, query)
+            .await
+            .and_then(FromObject::from_object)
+    }
+    /// Query a database and yield every matching page, fetching more as the stream is polled
+    pub fn databases_query_stream(
+        &self,
+        id: DatabaseId,
+        query: Option<DatabaseQuery>,
+    ) -> impl Stream<Item = Result<Page, Error>> + '_ {
+        let query = query.unwrap_or_default();
+        paginate(move |cursor| {
+            let id = id.clone();
+            let query = query.clone().start_from(cursor);
+            async move { self.databases_query(id, Some(query)).await }
+        })
+    }
+    /// Create a database
+    pub async fn databases_create(&self, db: CreateDatabase) -> Result<Database, Error> {
+        self.raw_databases_create(db)
+            .await
+            .and_then(FromObject::from_object)
+    }
+    /// Update a database
+    pub async fn databases_update(
+        &self,
+        id: DatabaseId,
+        db: UpdateDatabase,
+    ) -> Result<Database, Error> {
+        self.raw_databases_update(id, db)
+            .await
+            .and_then(FromObject::from_object)
+    }
+}
+
+impl NotionApi {
+    pub async fn raw_databases_retrieve(&self, id: DatabaseId) -> Result<Object, Error> {
         let u = format!("{}/{}/{}", self.base_path, ROUTER, id);
         self.request(self.client.get(u)).await
     }
-    pub async fn databases_query(
+    pub async fn raw_databases_query(
         &self,
         id: DatabaseId,
         query: Option<DatabaseQuery>,
@@ -23,11 +73,11 @@ impl NotionApi {
         }
         self.request(rb).await
     }
-    pub async fn databases_create(&self, db: CreateDatabase) -> Result<Object, Error> {
+    pub async fn raw_databases_create(&self, db: CreateDatabase) -> Result<Object, Error> {
         let u = format!("{}/{}/", self.base_path, ROUTER);
         self.request(self.client.post(u).json(&db)).await
     }
-    pub async fn databases_update(
+    pub async fn raw_databases_update(
         &self,
         id: DatabaseId,
         db: UpdateDatabase,
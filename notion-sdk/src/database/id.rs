@@ -33,6 +33,13 @@ impl Display for PropertyId {
     }
 }
 
+impl PropertyId {
+    /// Properties written by the client have no id yet
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
 impl std::str::FromStr for PropertyId {
     type Err = Error;
 
@@ -0,0 +1,486 @@
+//!
+//! # Property Kinds
+//!
+//! One marker type per property type, used by `#[derive(NotionPage)]`
+//! to check at compile time that a field can hold the property it is mapped to.
+//! [`FromProperty`] reads a field from a [`PropertyValue`], [`IntoProperty`] writes it back.
+//! Formulas, rollups, files and the created/last edited properties can only be read.
+//! `Option<T>` fields are `None` when the property is missing or empty, and clear it when written.
+use crate::common::rich_text::RichText;
+use crate::database::date::{DateValue, FormulaResultValue};
+use crate::database::files::FileReference;
+use crate::database::id::PropertyId;
+use crate::database::properties::{FromPropertyValue, PropertyValue};
+use crate::database::relation::RelationValue;
+use crate::database::rollup::RollupValue;
+use crate::database::select::SelectedValue;
+use crate::database::Color;
+use crate::user::User;
+use chrono::{DateTime, Utc};
+
+/// A property type, see the [module documentation](self).
+pub trait PropertyKind {
+    /// Name of the type in the Notion API
+    const NAME: &'static str;
+    /// Whether `value` is of this type
+    fn matches(value: &PropertyValue) -> bool;
+}
+
+/// A property type pages can be created and updated with.
+pub trait WritableKind: PropertyKind {
+    /// The value clearing the property
+    fn empty() -> PropertyValue;
+}
+
+/// Read a field from a property of kind `K`.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can not be read from a `{K}` property",
+    label = "unsupported field type for this `kind`"
+)]
+pub trait FromProperty<K: PropertyKind>: Sized {
+    /// `None` if the property is empty or of another type
+    fn from_property(value: &PropertyValue) -> Option<Self>;
+    /// Value of the field when the page has no such property
+    fn missing() -> Option<Self> {
+        None
+    }
+}
+
+/// Write a field to a property of kind `K`.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can not be written to a `{K}` property",
+    label = "unsupported field type for this `kind`"
+)]
+pub trait IntoProperty<K: WritableKind> {
+    fn to_property(&self) -> PropertyValue;
+}
+
+impl<K: PropertyKind, T: FromProperty<K>> FromProperty<K> for Option<T> {
+    fn from_property(value: &PropertyValue) -> Option<Self> {
+        Some(T::from_property(value))
+    }
+    fn missing() -> Option<Self> {
+        Some(None)
+    }
+}
+
+impl<K: WritableKind, T: IntoProperty<K>> IntoProperty<K> for Option<T> {
+    fn to_property(&self) -> PropertyValue {
+        match self {
+            Some(value) => value.to_property(),
+            None => K::empty(),
+        }
+    }
+}
+
+macro_rules! kind {
+    ($(#[$doc:meta])* $kind:ident, $name:literal, $variant:ident) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy)]
+        pub struct $kind;
+
+        impl PropertyKind for $kind {
+            const NAME: &'static str = $name;
+            fn matches(value: &PropertyValue) -> bool {
+                matches!(value, PropertyValue::$variant { .. })
+            }
+        }
+    };
+}
+
+/// Read types which [`FromPropertyValue`] already supports
+macro_rules! readable {
+    ($kind:ident: $($ty:ty),+) => {
+        $(
+            impl FromProperty<$kind> for $ty {
+                fn from_property(value: &PropertyValue) -> Option<Self> {
+                    if $kind::matches(value) {
+                        FromPropertyValue::from_property_value(value)
+                    } else {
+                        None
+                    }
+                }
+            }
+        )+
+    };
+}
+
+kind!(Title, "title", Title);
+kind!(
+    /// `rich_text` property
+    Text,
+    "rich_text",
+    Text
+);
+kind!(Number, "number", Number);
+kind!(Select, "select", Select);
+kind!(Status, "status", Status);
+kind!(MultiSelect, "multi_select", MultiSelect);
+kind!(Date, "date", Date);
+kind!(People, "people", People);
+kind!(Files, "files", Files);
+kind!(Checkbox, "checkbox", Checkbox);
+kind!(Url, "url", Url);
+kind!(Email, "email", Email);
+kind!(PhoneNumber, "phone_number", PhoneNumber);
+kind!(Formula, "formula", Formula);
+kind!(Relation, "relation", Relation);
+kind!(Rollup, "rollup", Rollup);
+kind!(CreatedTime, "created_time", CreatedTime);
+kind!(CreatedBy, "created_by", CreatedBy);
+kind!(LastEditedTime, "last_edited_time", LastEditedTime);
+kind!(LastEditedBy, "last_edited_by", LastEditedBy);
+
+readable!(Title: String, Vec<RichText>, PropertyValue);
+readable!(Text: String, Vec<RichText>, PropertyValue);
+readable!(Number: serde_json::Number, f64, i64, PropertyValue);
+readable!(Select: SelectedValue, PropertyValue);
+readable!(Status: SelectedValue, PropertyValue);
+readable!(MultiSelect: Vec<SelectedValue>, PropertyValue);
+readable!(Date: DateValue, PropertyValue);
+readable!(People: Vec<User>, PropertyValue);
+readable!(Files: Vec<FileReference>, PropertyValue);
+readable!(Checkbox: bool, PropertyValue);
+readable!(Url: String, PropertyValue);
+readable!(Email: String, PropertyValue);
+readable!(PhoneNumber: String, PropertyValue);
+readable!(Formula: FormulaResultValue, PropertyValue);
+readable!(Relation: Vec<RelationValue>, PropertyValue);
+readable!(Rollup: RollupValue, PropertyValue);
+readable!(CreatedTime: DateTime<Utc>, PropertyValue);
+readable!(CreatedBy: User, PropertyValue);
+readable!(LastEditedTime: DateTime<Utc>, PropertyValue);
+readable!(LastEditedBy: User, PropertyValue);
+
+/// name of the selected option
+impl FromProperty<Select> for String {
+    fn from_property(value: &PropertyValue) -> Option<Self> {
+        SelectedValue::from_property_value(value)?.name
+    }
+}
+
+/// name of the status
+impl FromProperty<Status> for String {
+    fn from_property(value: &PropertyValue) -> Option<Self> {
+        SelectedValue::from_property_value(value)?.name
+    }
+}
+
+/// names of the selected options
+impl FromProperty<MultiSelect> for Vec<String> {
+    fn from_property(value: &PropertyValue) -> Option<Self> {
+        let options = Vec::<SelectedValue>::from_property_value(value)?;
+        Some(
+            options
+                .into_iter()
+                .filter_map(|option| option.name)
+                .collect(),
+        )
+    }
+}
+
+fn option(name: &str) -> SelectedValue {
+    SelectedValue {
+        id: None,
+        name: Some(name.to_string()),
+        color: Color::Default,
+    }
+}
+
+impl WritableKind for Title {
+    fn empty() -> PropertyValue {
+        PropertyValue::Title {
+            id: PropertyId::default(),
+            title: vec![],
+        }
+    }
+}
+
+impl IntoProperty<Title> for String {
+    fn to_property(&self) -> PropertyValue {
+        PropertyValue::Title {
+            id: PropertyId::default(),
+            title: vec![RichText::text(self)],
+        }
+    }
+}
+
+impl IntoProperty<Title> for Vec<RichText> {
+    fn to_property(&self) -> PropertyValue {
+        PropertyValue::Title {
+            id: PropertyId::default(),
+            title: self.clone(),
+        }
+    }
+}
+
+impl WritableKind for Text {
+    fn empty() -> PropertyValue {
+        PropertyValue::Text {
+            id: PropertyId::default(),
+            rich_text: vec![],
+        }
+    }
+}
+
+impl IntoProperty<Text> for String {
+    fn to_property(&self) -> PropertyValue {
+        PropertyValue::Text {
+            id: PropertyId::default(),
+            rich_text: vec![RichText::text(self)],
+        }
+    }
+}
+
+impl IntoProperty<Text> for Vec<RichText> {
+    fn to_property(&self) -> PropertyValue {
+        PropertyValue::Text {
+            id: PropertyId::default(),
+            rich_text: self.clone(),
+        }
+    }
+}
+
+impl WritableKind for Number {
+    fn empty() -> PropertyValue {
+        PropertyValue::Number {
+            id: PropertyId::default(),
+            number: None,
+        }
+    }
+}
+
+impl IntoProperty<Number> for serde_json::Number {
+    fn to_property(&self) -> PropertyValue {
+        PropertyValue::Number {
+            id: PropertyId::default(),
+            number: Some(self.clone()),
+        }
+    }
+}
+
+/// `NaN` and infinities clear the property
+impl IntoProperty<Number> for f64 {
+    fn to_property(&self) -> PropertyValue {
+        PropertyValue::Number {
+            id: PropertyId::default(),
+            number: serde_json::Number::from_f64(*self),
+        }
+    }
+}
+
+impl IntoProperty<Number> for i64 {
+    fn to_property(&self) -> PropertyValue {
+        PropertyValue::Number {
+            id: PropertyId::default(),
+            number: Some((*self).into()),
+        }
+    }
+}
+
+impl WritableKind for Select {
+    fn empty() -> PropertyValue {
+        PropertyValue::Select {
+            id: PropertyId::default(),
+            select: None,
+        }
+    }
+}
+
+impl IntoProperty<Select> for String {
+    fn to_property(&self) -> PropertyValue {
+        PropertyValue::Select {
+            id: PropertyId::default(),
+            select: Some(option(self)),
+        }
+    }
+}
+
+impl IntoProperty<Select> for SelectedValue {
+    fn to_property(&self) -> PropertyValue {
+        PropertyValue::Select {
+            id: PropertyId::default(),
+            select: Some(self.clone()),
+        }
+    }
+}
+
+impl WritableKind for Status {
+    fn empty() -> PropertyValue {
+        PropertyValue::Status {
+            id: PropertyId::default(),
+            status: None,
+        }
+    }
+}
+
+impl IntoProperty<Status> for String {
+    fn to_property(&self) -> PropertyValue {
+        PropertyValue::Status {
+            id: PropertyId::default(),
+            status: Some(option(self)),
+        }
+    }
+}
+
+impl IntoProperty<Status> for SelectedValue {
+    fn to_property(&self) -> PropertyValue {
+        PropertyValue::Status {
+            id: PropertyId::default(),
+            status: Some(self.clone()),
+        }
+    }
+}
+
+impl WritableKind for MultiSelect {
+    fn empty() -> PropertyValue {
+        PropertyValue::MultiSelect {
+            id: PropertyId::default(),
+            multi_select: Some(vec![]),
+        }
+    }
+}
+
+impl IntoProperty<MultiSelect> for Vec<String> {
+    fn to_property(&self) -> PropertyValue {
+        PropertyValue::MultiSelect {
+            id: PropertyId::default(),
+            multi_select: Some(self.iter().map(|name| option(name)).collect()),
+        }
+    }
+}
+
+impl IntoProperty<MultiSelect> for Vec<SelectedValue> {
+    fn to_property(&self) -> PropertyValue {
+        PropertyValue::MultiSelect {
+            id: PropertyId::default(),
+            multi_select: Some(self.clone()),
+        }
+    }
+}
+
+impl WritableKind for Date {
+    fn empty() -> PropertyValue {
+        PropertyValue::Date {
+            id: PropertyId::default(),
+            date: None,
+        }
+    }
+}
+
+impl IntoProperty<Date> for DateValue {
+    fn to_property(&self) -> PropertyValue {
+        PropertyValue::Date {
+            id: PropertyId::default(),
+            date: Some(self.clone()),
+        }
+    }
+}
+
+impl WritableKind for People {
+    fn empty() -> PropertyValue {
+        PropertyValue::People {
+            id: PropertyId::default(),
+            people: vec![],
+        }
+    }
+}
+
+impl IntoProperty<People> for Vec<User> {
+    fn to_property(&self) -> PropertyValue {
+        PropertyValue::People {
+            id: PropertyId::default(),
+            people: self.clone(),
+        }
+    }
+}
+
+impl WritableKind for Checkbox {
+    fn empty() -> PropertyValue {
+        PropertyValue::Checkbox {
+            id: PropertyId::default(),
+            checkbox: false,
+        }
+    }
+}
+
+impl IntoProperty<Checkbox> for bool {
+    fn to_property(&self) -> PropertyValue {
+        PropertyValue::Checkbox {
+            id: PropertyId::default(),
+            checkbox: *self,
+        }
+    }
+}
+
+impl WritableKind for Url {
+    fn empty() -> PropertyValue {
+        PropertyValue::Url {
+            id: PropertyId::default(),
+            url: None,
+        }
+    }
+}
+
+impl IntoProperty<Url> for String {
+    fn to_property(&self) -> PropertyValue {
+        PropertyValue::Url {
+            id: PropertyId::default(),
+            url: Some(self.clone()),
+        }
+    }
+}
+
+impl WritableKind for Email {
+    fn empty() -> PropertyValue {
+        PropertyValue::Email {
+            id: PropertyId::default(),
+            email: None,
+        }
+    }
+}
+
+impl IntoProperty<Email> for String {
+    fn to_property(&self) -> PropertyValue {
+        PropertyValue::Email {
+            id: PropertyId::default(),
+            email: Some(self.clone()),
+        }
+    }
+}
+
+impl WritableKind for PhoneNumber {
+    fn empty() -> PropertyValue {
+        PropertyValue::PhoneNumber {
+            id: PropertyId::default(),
+            phone_number: None,
+        }
+    }
+}
+
+impl IntoProperty<PhoneNumber> for String {
+    fn to_property(&self) -> PropertyValue {
+        PropertyValue::PhoneNumber {
+            id: PropertyId::default(),
+            phone_number: Some(self.clone()),
+        }
+    }
+}
+
+impl WritableKind for Relation {
+    fn empty() -> PropertyValue {
+        PropertyValue::Relation {
+            id: PropertyId::default(),
+            relation: Some(vec![]),
+        }
+    }
+}
+
+impl IntoProperty<Relation> for Vec<RelationValue> {
+    fn to_property(&self) -> PropertyValue {
+        PropertyValue::Relation {
+            id: PropertyId::default(),
+            relation: Some(self.clone()),
+        }
+    }
+}
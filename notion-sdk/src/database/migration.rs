@@ -0,0 +1,591 @@
+//!
+//! # Schema Migration
+//!
+//! Describe the properties a database should have with a [`TargetSchema`],
+//! compare it with a live [`Database`] and apply the resulting [`MigrationPlan`]
+//! with [`databases_update`](crate::NotionApi::databases_update).
+//! Steps which lose data, such as deleting a property, are marked as destructive.
+//!
+//! ## Examples
+//! ```rust,no_run
+//! use notion_sdk::database::migration::TargetSchema;
+//! use notion_sdk::database::number::NumberFormat;
+//! use notion_sdk::database::schema::PropertySchema;
+//! use notion_sdk::NotionApi;
+//! # async fn run() -> Result<(), notion_sdk::error::Error> {
+//! let notion = NotionApi::new("token")?;
+//! let database_id: notion_sdk::database::id::DatabaseId = "3f8d5d7e-6c0a-4d9b-9a51-9f3c2b1e8a10".parse()?;
+//! let target = TargetSchema::new()
+//!     .property("Name", PropertySchema::title())
+//!     .property("Points", PropertySchema::number(NumberFormat::Number))
+//!     .renamed_from("Points", "Estimate")
+//!     .property("Priority", PropertySchema::select(["High", "Medium", "Low"]))
+//!     .prune();
+//! let database = notion.databases_retrieve(database_id.clone()).await?;
+//! let plan = database.plan_migration(&target);
+//! // dry run
+//! print!("{plan}");
+//! if !plan.is_empty() && !plan.is_destructive() {
+//!     notion.databases_update(database_id, plan.to_update()).await?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use crate::database::number::NumberFormat;
+use crate::database::properties::PropertyConfiguration;
+use crate::database::schema::{
+    PropertySchema, PropertySchemaKind, RelationKind, SelectOptionSchema, SelectSchema,
+};
+use crate::database::{Database, UpdateDatabase};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+
+/// The properties a database should have.
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct TargetSchema {
+    properties: BTreeMap<String, TargetProperty>,
+    prune: bool,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct TargetProperty {
+    kind: PropertySchemaKind,
+    previous_names: Vec<String>,
+}
+
+impl TargetSchema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// The database should have a property `name` configured like `schema`,
+    /// the `name` of the schema itself is ignored.
+    pub fn property<T: Into<String>>(mut self, name: T, schema: PropertySchema) -> Self {
+        let Some(kind) = schema.kind else {
+            return self;
+        };
+        self.properties
+            .entry(name.into())
+            .and_modify(|property| property.kind = kind.clone())
+            .or_insert(TargetProperty {
+                kind,
+                previous_names: vec![],
+            });
+        self
+    }
+    /// Rename the property `previous_name` to `name` instead of adding `name`,
+    /// ignored unless `name` was added with [`property`](Self::property).
+    pub fn renamed_from<T: Into<String>, P: Into<String>>(
+        mut self,
+        name: T,
+        previous_name: P,
+    ) -> Self {
+        if let Some(property) = self.properties.get_mut(&name.into()) {
+            property.previous_names.push(previous_name.into());
+        }
+        self
+    }
+    /// Delete the properties of the database which are not part of the target,
+    /// except for the title and status properties, which can not be deleted or described.
+    pub fn prune(mut self) -> Self {
+        self.prune = true;
+        self
+    }
+}
+
+/// One change to the schema of a database.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum MigrationStep {
+    AddProperty {
+        name: String,
+        kind: PropertySchemaKind,
+    },
+    RenameProperty {
+        from: String,
+        to: String,
+    },
+    /// Notion converts the values it can, the others are lost.
+    ChangeType {
+        name: String,
+        from: &'static str,
+        to: PropertySchemaKind,
+    },
+    ChangeNumberFormat {
+        name: String,
+        from: NumberFormat,
+        to: NumberFormat,
+    },
+    AddSelectOptions {
+        name: String,
+        options: Vec<String>,
+    },
+    /// Pages lose the removed options.
+    RemoveSelectOptions {
+        name: String,
+        options: Vec<String>,
+    },
+    /// New formula, relation or rollup configuration.
+    Reconfigure {
+        name: String,
+        kind: PropertySchemaKind,
+    },
+    DeleteProperty {
+        name: String,
+    },
+}
+
+impl MigrationStep {
+    /// Whether the step loses data stored in pages.
+    pub fn is_destructive(&self) -> bool {
+        match self {
+            MigrationStep::ChangeType { .. }
+            | MigrationStep::RemoveSelectOptions { .. }
+            | MigrationStep::DeleteProperty { .. } => true,
+            MigrationStep::Reconfigure { kind, .. } => {
+                matches!(kind, PropertySchemaKind::Relation(_))
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Display for MigrationStep {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MigrationStep::AddProperty { name, kind } => {
+                write!(f, "add {} property {name:?}", kind.type_name())
+            }
+            MigrationStep::RenameProperty { from, to } => {
+                write!(f, "rename property {from:?} to {to:?}")
+            }
+            MigrationStep::ChangeType { name, from, to } => write!(
+                f,
+                "change type of property {name:?} from {from} to {}",
+                to.type_name()
+            ),
+            MigrationStep::ChangeNumberFormat { name, from, to } => write!(
+                f,
+                "change number format of property {name:?} from {from:?} to {to:?}"
+            ),
+            MigrationStep::AddSelectOptions { name, options } => {
+                write!(f, "add options {options:?} to property {name:?}")
+            }
+            MigrationStep::RemoveSelectOptions { name, options } => {
+                write!(f, "remove options {options:?} from property {name:?}")
+            }
+            MigrationStep::Reconfigure { name, kind } => {
+                write!(f, "reconfigure {} property {name:?}", kind.type_name())
+            }
+            MigrationStep::DeleteProperty { name } => write!(f, "delete property {name:?}"),
+        }
+    }
+}
+
+/// Steps converging a database to a [`TargetSchema`], and the update performing them.
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct MigrationPlan {
+    pub steps: Vec<MigrationStep>,
+    /// payload of the update, by current property name
+    properties: HashMap<String, Option<PropertySchema>>,
+}
+
+impl MigrationPlan {
+    /// Whether the database already matches the target.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+    /// Whether any step loses data stored in pages.
+    pub fn is_destructive(&self) -> bool {
+        self.steps.iter().any(MigrationStep::is_destructive)
+    }
+    /// Steps which lose data stored in pages.
+    pub fn destructive_steps(&self) -> impl Iterator<Item = &MigrationStep> {
+        self.steps.iter().filter(|step| step.is_destructive())
+    }
+    /// The update applying every step at once.
+    pub fn to_update(&self) -> UpdateDatabase {
+        UpdateDatabase {
+            title: None,
+            properties: self.properties.clone(),
+        }
+    }
+    fn push(&mut self, key: &str, step: MigrationStep) {
+        let entry = self.properties.entry(key.to_string()).or_insert_with(|| {
+            Some(PropertySchema {
+                name: None,
+                kind: None,
+            })
+        });
+        match &step {
+            MigrationStep::RenameProperty { to, .. } => {
+                if let Some(schema) = entry {
+                    schema.name = Some(to.clone());
+                }
+            }
+            MigrationStep::DeleteProperty { .. } => *entry = None,
+            _ => {}
+        }
+        self.steps.push(step);
+    }
+    fn configure(&mut self, key: &str, kind: PropertySchemaKind) {
+        if let Some(Some(schema)) = self.properties.get_mut(key) {
+            schema.kind = Some(kind);
+        }
+    }
+}
+
+/// Lists every step, destructive ones are marked with `!`.
+impl Display for MigrationPlan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.steps.is_empty() {
+            return writeln!(f, "  nothing to do");
+        }
+        for step in &self.steps {
+            let marker = if step.is_destructive() { '!' } else { ' ' };
+            writeln!(f, "{marker} {step}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Database {
+    /// The steps converging this database to `target`.
+    pub fn plan_migration(&self, target: &TargetSchema) -> MigrationPlan {
+        let current_of = self.match_properties(target);
+        let mut plan = MigrationPlan::default();
+        for (name, property) in &target.properties {
+            let Some((current_name, current)) = current_of
+                .get(name.as_str())
+                .and_then(|key| self.properties.get_key_value(*key))
+            else {
+                plan.properties.insert(
+                    name.clone(),
+                    Some(PropertySchema {
+                        name: None,
+                        kind: Some(property.kind.clone()),
+                    }),
+                );
+                plan.steps.push(MigrationStep::AddProperty {
+                    name: name.clone(),
+                    kind: property.kind.clone(),
+                });
+                continue;
+            };
+            if current_name != name {
+                plan.push(
+                    current_name,
+                    MigrationStep::RenameProperty {
+                        from: current_name.clone(),
+                        to: name.clone(),
+                    },
+                );
+            }
+            diff_property(&mut plan, current_name, name, current, &property.kind);
+        }
+        if target.prune {
+            let matched: HashSet<&str> = current_of.values().copied().collect();
+            let mut unmatched: Vec<&String> = self
+                .properties
+                .iter()
+                // the title can not be deleted, a status can not be described by a target
+                .filter(|(name, current)| {
+                    !matched.contains(name.as_str())
+                        && !matches!(
+                            current,
+                            PropertyConfiguration::Title { .. }
+                                | PropertyConfiguration::Status { .. }
+                        )
+                })
+                .map(|(name, _)| name)
+                .collect();
+            unmatched.sort();
+            for name in unmatched {
+                plan.push(name, MigrationStep::DeleteProperty { name: name.clone() });
+            }
+        }
+        plan
+    }
+
+    /// The current property each target property becomes, by exact name first,
+    /// then by a previous name and for the title by type, never taking a property twice.
+    fn match_properties<'a>(&'a self, target: &'a TargetSchema) -> HashMap<&'a str, &'a str> {
+        let mut current_of: HashMap<&str, &str> = HashMap::new();
+        for name in target.properties.keys() {
+            if let Some((key, _)) = self.properties.get_key_value(name) {
+                current_of.insert(name, key);
+            }
+        }
+        let taken = |current_of: &HashMap<&str, &str>, key: &str| {
+            current_of.values().any(|matched| *matched == key)
+        };
+        for (name, property) in &target.properties {
+            if current_of.contains_key(name.as_str()) {
+                continue;
+            }
+            let previous = property
+                .previous_names
+                .iter()
+                .filter_map(|previous| self.properties.get_key_value(previous))
+                .map(|(key, _)| key.as_str())
+                .find(|key| !taken(&current_of, key));
+            if let Some(key) = previous {
+                current_of.insert(name, key);
+            }
+        }
+        // every database has exactly one title property
+        let title = target.properties.iter().find(|(name, property)| {
+            !current_of.contains_key(name.as_str())
+                && matches!(property.kind, PropertySchemaKind::Title(_))
+        });
+        if let Some((name, _)) = title {
+            let current = self.properties.iter().find(|(key, current)| {
+                matches!(current, PropertyConfiguration::Title { .. }) && !taken(&current_of, key)
+            });
+            if let Some((key, _)) = current {
+                current_of.insert(name, key);
+            }
+        }
+        current_of
+    }
+}
+
+/// Compare the configuration of a property which exists under `key` and is called `name` in the target.
+fn diff_property(
+    plan: &mut MigrationPlan,
+    key: &str,
+    name: &str,
+    current: &PropertyConfiguration,
+    target: &PropertySchemaKind,
+) {
+    if current.type_name() != target.type_name() {
+        plan.push(
+            key,
+            MigrationStep::ChangeType {
+                name: name.to_string(),
+                from: current.type_name(),
+                to: target.clone(),
+            },
+        );
+        plan.configure(key, target.clone());
+        return;
+    }
+    match (current, target) {
+        (PropertyConfiguration::Number { number, .. }, PropertySchemaKind::Number(details))
+            if number.format != details.format =>
+        {
+            plan.push(
+                key,
+                MigrationStep::ChangeNumberFormat {
+                    name: name.to_string(),
+                    from: number.format,
+                    to: details.format,
+                },
+            );
+            plan.configure(key, target.clone());
+        }
+        (
+            PropertyConfiguration::Select { select, .. }
+            | PropertyConfiguration::MultiSelect {
+                multi_select: select,
+                ..
+            },
+            PropertySchemaKind::Select(wanted) | PropertySchemaKind::MultiSelect(wanted),
+        ) => {
+            let existing: Vec<&str> = select.options.iter().map(|o| o.name.as_str()).collect();
+            let added: Vec<String> = wanted
+                .options
+                .iter()
+                .filter(|option| !existing.contains(&option.name.as_str()))
+                .map(|option| option.name.clone())
+                .collect();
+            let removed: Vec<String> = existing
+                .iter()
+                .filter(|option| !wanted.options.iter().any(|w| w.name == **option))
+                .map(|option| option.to_string())
+                .collect();
+            if added.is_empty() && removed.is_empty() {
+                return;
+            }
+            if !added.is_empty() {
+                plan.push(
+                    key,
+                    MigrationStep::AddSelectOptions {
+                        name: name.to_string(),
+                        options: added,
+                    },
+                );
+            }
+            if !removed.is_empty() {
+                plan.push(
+                    key,
+                    MigrationStep::RemoveSelectOptions {
+                        name: name.to_string(),
+                        options: removed,
+                    },
+                );
+            }
+            // the options sent replace the current ones, keep the colors of those which stay
+            let options = SelectSchema {
+                options: wanted
+                    .options
+                    .iter()
+                    .map(|option| SelectOptionSchema {
+                        name: option.name.clone(),
+                        color: option.color.or_else(|| {
+                            select
+                                .options
+                                .iter()
+                                .find(|current| current.name == option.name)
+                                .map(|current| current.color)
+                        }),
+                    })
+                    .collect(),
+            };
+            let kind = match target {
+                PropertySchemaKind::Select(_) => PropertySchemaKind::Select(options),
+                _ => PropertySchemaKind::MultiSelect(options),
+            };
+            plan.configure(key, kind);
+        }
+        (
+            PropertyConfiguration::Formula { .. }
+            | PropertyConfiguration::Relation { .. }
+            | PropertyConfiguration::Rollup { .. },
+            _,
+        ) => {
+            let unchanged = current
+                .to_schema()
+                .and_then(|schema| schema.kind)
+                .is_some_and(|kind| same_configuration(&kind, target));
+            if !unchanged {
+                plan.push(
+                    key,
+                    MigrationStep::Reconfigure {
+                        name: name.to_string(),
+                        kind: target.clone(),
+                    },
+                );
+                plan.configure(key, target.clone());
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Formulas, relations and rollups which would not change, ignoring what Notion fills in itself
+fn same_configuration(current: &PropertySchemaKind, target: &PropertySchemaKind) -> bool {
+    match (current, target) {
+        (PropertySchemaKind::Relation(current), PropertySchemaKind::Relation(target)) => {
+            current.database_id == target.database_id
+                && match (&current.kind, &target.kind) {
+                    (RelationKind::SingleProperty { .. }, RelationKind::SingleProperty { .. }) => {
+                        true
+                    }
+                    (
+                        RelationKind::DualProperty {
+                            dual_property: current,
+                        },
+                        RelationKind::DualProperty {
+                            dual_property: target,
+                        },
+                    ) => {
+                        target.synced_property_name.is_none()
+                            || current.synced_property_name == target.synced_property_name
+                    }
+                    _ => false,
+                }
+        }
+        (PropertySchemaKind::Rollup(current), PropertySchemaKind::Rollup(target)) => {
+            current.function == target.function
+                && (target.relation_property_name.is_none()
+                    || current.relation_property_name == target.relation_property_name)
+                && (target.rollup_property_name.is_none()
+                    || current.rollup_property_name == target.rollup_property_name)
+        }
+        (current, target) => current == target,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::database;
+    use serde_json::json;
+
+    const RELATED: &str = "7b2f7c1e-5d0a-4c3e-9f1b-2a6d8e4c0b13";
+
+    #[test]
+    fn pruning_keeps_title_and_status() {
+        let database = database(json!({
+            "Title": {"id": "title", "name": "Title", "type": "title", "title": {}},
+            "State": {"id": "a", "name": "State", "type": "status", "status": {"options": [], "groups": []}},
+            "Notes": {"id": "b", "name": "Notes", "type": "rich_text", "rich_text": {}},
+        }));
+        let plan = database.plan_migration(&TargetSchema::new().prune());
+        assert_eq!(
+            plan.steps,
+            vec![MigrationStep::DeleteProperty {
+                name: "Notes".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn title_is_not_taken_from_a_property_matched_by_name() {
+        let database = database(json!({
+            "Name": {"id": "title", "name": "Name", "type": "title", "title": {}},
+        }));
+        let target = TargetSchema::new()
+            .property("Name", PropertySchema::rich_text())
+            .property("Task", PropertySchema::title());
+        let plan = database.plan_migration(&target);
+        assert!(plan
+            .steps
+            .iter()
+            .all(|step| !matches!(step, MigrationStep::RenameProperty { .. })));
+        assert!(plan.steps.contains(&MigrationStep::AddProperty {
+            name: "Task".to_string(),
+            kind: PropertySchema::title().kind.unwrap(),
+        }));
+    }
+
+    #[test]
+    fn title_is_renamed_when_the_target_calls_it_otherwise() {
+        let database = database(json!({
+            "Name": {"id": "title", "name": "Name", "type": "title", "title": {}},
+        }));
+        let plan =
+            database.plan_migration(&TargetSchema::new().property("Task", PropertySchema::title()));
+        assert_eq!(
+            plan.steps,
+            vec![MigrationStep::RenameProperty {
+                from: "Name".to_string(),
+                to: "Task".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn switching_between_single_and_dual_relation_reconfigures() {
+        let database = database(json!({
+            "Name": {"id": "title", "name": "Name", "type": "title", "title": {}},
+            "Tasks": {"id": "c", "name": "Tasks", "type": "relation", "relation": {
+                "database_id": RELATED,
+                "synced_property_name": null,
+                "synced_property_id": null,
+            }},
+        }));
+        let single = TargetSchema::new()
+            .property("Name", PropertySchema::title())
+            .property("Tasks", PropertySchema::relation(RELATED.parse().unwrap()));
+        assert!(database.plan_migration(&single).is_empty());
+        let dual = TargetSchema::new()
+            .property("Name", PropertySchema::title())
+            .property(
+                "Tasks",
+                PropertySchema::dual_relation(RELATED.parse().unwrap(), None),
+            );
+        let plan = database.plan_migration(&dual);
+        assert_eq!(plan.steps.len(), 1);
+        assert!(plan.is_destructive());
+    }
+}
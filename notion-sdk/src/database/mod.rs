@@ -5,11 +5,12 @@
 //! ```rust,no_run
 //! use notion_sdk::common::parent::Parent;
 //! use notion_sdk::database::CreateDatabase;
-//! use notion_sdk::database::properties::Properties;
+//! use notion_sdk::database::schema::PropertySchema;
+//! use std::collections::HashMap;
 //! let database = CreateDatabase{
 //!     parent: Parent::Workspace,
 //!     title: vec![],
-//!     properties: Properties { properties: Default::default()},
+//!     properties: HashMap::from([("Name".to_string(), PropertySchema::title())]),
 //! };
 //! ```
 pub mod properties;
@@ -18,7 +19,8 @@ use crate::common::file::FileOrEmojiObject;
 use crate::common::parent::Parent;
 use crate::common::rich_text::RichText;
 use crate::database::id::DatabaseId;
-use crate::database::properties::{Properties, PropertyConfiguration};
+use crate::database::properties::PropertyConfiguration;
+use crate::database::schema::PropertySchema;
 use crate::user::UserCommon;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -30,11 +32,15 @@ pub mod date;
 pub mod files;
 pub mod formula;
 pub mod id;
+pub mod kind;
+pub mod migration;
 pub mod number;
 pub mod relation;
 pub mod rollup;
+pub mod schema;
 pub mod select;
 pub mod status;
+pub mod validate;
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Copy, Clone)]
 #[serde(rename_all = "lowercase")]
@@ -86,11 +92,15 @@ pub struct Database {
 pub struct CreateDatabase {
     pub parent: Parent,
     pub title: Vec<RichText>,
-    pub properties: Properties,
+    /// Properties by name, one of them has to be the title.
+    pub properties: HashMap<String, PropertySchema>,
 }
 
-#[derive(Serialize, Debug, Eq, PartialEq)]
+#[derive(Serialize, Debug, Eq, PartialEq, Default)]
 pub struct UpdateDatabase {
-    pub title: Vec<RichText>,
-    pub properties: Properties,
+    /// New title, the title is kept if `None`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<Vec<RichText>>,
+    /// Properties to change by name or id, `None` deletes the property.
+    pub properties: HashMap<String, Option<PropertySchema>>,
 }
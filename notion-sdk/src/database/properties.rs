@@ -26,91 +26,123 @@ pub struct Properties {
 pub enum PropertyValue {
     // <https://developers.notion.com/reference/property-object#title-configuration>
     Title {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         title: Vec<RichText>,
     },
     /// <https://developers.notion.com/reference/property-object#text-configuration>
     #[serde(rename = "rich_text")]
     Text {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         rich_text: Vec<RichText>,
     },
     /// <https://developers.notion.com/reference/property-object#number-configuration>
     Number {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         number: Option<Number>,
     },
     /// <https://developers.notion.com/reference/property-object#select-configuration>
     Select {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         select: Option<SelectedValue>,
     },
     /// <https://developers.notion.com/reference/property-object#status-configuration>
     Status {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         status: Option<SelectedValue>,
     },
     /// <https://developers.notion.com/reference/property-object#multi-select-configuration>
     MultiSelect {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         multi_select: Option<Vec<SelectedValue>>,
     },
     /// <https://developers.notion.com/reference/property-object#date-configuration>
     Date {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         date: Option<DateValue>,
     },
     /// <https://developers.notion.com/reference/property-object#formula-configuration>
     Formula {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         formula: FormulaResultValue,
     },
     /// <https://developers.notion.com/reference/property-object#relation-configuration>
     /// It is actually an array of relations
     Relation {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         relation: Option<Vec<RelationValue>>,
     },
     /// <https://developers.notion.com/reference/property-object#rollup-configuration>
     Rollup {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         rollup: Option<RollupValue>,
     },
     /// <https://developers.notion.com/reference/property-object#people-configuration>
-    People { id: PropertyId, people: Vec<User> },
+    People {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
+        id: PropertyId,
+        people: Vec<User>,
+    },
     /// <https://developers.notion.com/reference/property-object#files-configuration>
     Files {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         files: Option<Vec<FileReference>>,
     },
     /// <https://developers.notion.com/reference/property-object#checkbox-configuration>
-    Checkbox { id: PropertyId, checkbox: bool },
+    Checkbox {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
+        id: PropertyId,
+        checkbox: bool,
+    },
     /// <https://developers.notion.com/reference/property-object#url-configuration>
-    Url { id: PropertyId, url: Option<String> },
+    Url {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
+        id: PropertyId,
+        url: Option<String>,
+    },
     /// <https://developers.notion.com/reference/property-object#email-configuration>
     Email {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         email: Option<String>,
     },
     /// <https://developers.notion.com/reference/property-object#phone-number-configuration>
     PhoneNumber {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
-        phone_number: String,
+        phone_number: Option<String>,
     },
     /// <https://developers.notion.com/reference/property-object#created-time-configuration>
     CreatedTime {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         created_time: DateTime<Utc>,
     },
     /// <https://developers.notion.com/reference/property-object#created-by-configuration>
-    CreatedBy { id: PropertyId, created_by: User },
+    CreatedBy {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
+        id: PropertyId,
+        created_by: User,
+    },
     /// <https://developers.notion.com/reference/property-object#last-edited-time-configuration>
     LastEditedTime {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         last_edited_time: DateTime<Utc>,
     },
     /// <https://developers.notion.com/reference/property-object#last-edited-by-configuration>
     LastEditedBy {
+        #[serde(default, skip_serializing_if = "PropertyId::is_empty")]
         id: PropertyId,
         last_edited_by: User,
     },
@@ -203,5 +235,184 @@ pub enum PropertyConfiguration {
     /// See <https://developers.notion.com/reference/database#last-edited-time-configuration>
     LastEditedTime { id: PropertyId, name: String },
     /// See <https://developers.notion.com/reference/database#last-edited-by-configuration>
-    LastEditBy { id: PropertyId, name: String },
+    LastEditedBy { id: PropertyId, name: String },
+}
+
+/// Values which can be read from a page property, see [`Page::property`](crate::pages::Page::property)
+pub trait FromPropertyValue: Sized {
+    /// `None` if the property is of another type or empty
+    fn from_property_value(value: &PropertyValue) -> Option<Self>;
+}
+
+impl FromPropertyValue for PropertyValue {
+    fn from_property_value(value: &PropertyValue) -> Option<Self> {
+        Some(value.clone())
+    }
+}
+
+/// title and text
+impl FromPropertyValue for Vec<RichText> {
+    fn from_property_value(value: &PropertyValue) -> Option<Self> {
+        match value {
+            PropertyValue::Title { title: text, .. }
+            | PropertyValue::Text {
+                rich_text: text, ..
+            } => Some(text.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// plain text of title and text, url, email and phone number
+impl FromPropertyValue for String {
+    fn from_property_value(value: &PropertyValue) -> Option<Self> {
+        match value {
+            PropertyValue::Title { title: text, .. }
+            | PropertyValue::Text {
+                rich_text: text, ..
+            } => Some(text.iter().map(RichText::plain_text).collect()),
+            PropertyValue::Url { url: text, .. } | PropertyValue::Email { email: text, .. } => {
+                text.clone()
+            }
+            PropertyValue::PhoneNumber { phone_number, .. } => phone_number.clone(),
+            _ => None,
+        }
+    }
+}
+
+impl FromPropertyValue for Number {
+    fn from_property_value(value: &PropertyValue) -> Option<Self> {
+        match value {
+            PropertyValue::Number { number, .. } => number.clone(),
+            _ => None,
+        }
+    }
+}
+
+impl FromPropertyValue for f64 {
+    fn from_property_value(value: &PropertyValue) -> Option<Self> {
+        Number::from_property_value(value)?.as_f64()
+    }
+}
+
+impl FromPropertyValue for i64 {
+    fn from_property_value(value: &PropertyValue) -> Option<Self> {
+        Number::from_property_value(value)?.as_i64()
+    }
+}
+
+impl FromPropertyValue for bool {
+    fn from_property_value(value: &PropertyValue) -> Option<Self> {
+        match value {
+            PropertyValue::Checkbox { checkbox, .. } => Some(*checkbox),
+            _ => None,
+        }
+    }
+}
+
+/// select and status
+impl FromPropertyValue for SelectedValue {
+    fn from_property_value(value: &PropertyValue) -> Option<Self> {
+        match value {
+            PropertyValue::Select { select: option, .. }
+            | PropertyValue::Status { status: option, .. } => option.clone(),
+            _ => None,
+        }
+    }
+}
+
+impl FromPropertyValue for Vec<SelectedValue> {
+    fn from_property_value(value: &PropertyValue) -> Option<Self> {
+        match value {
+            PropertyValue::MultiSelect { multi_select, .. } => multi_select.clone(),
+            _ => None,
+        }
+    }
+}
+
+impl FromPropertyValue for DateValue {
+    fn from_property_value(value: &PropertyValue) -> Option<Self> {
+        match value {
+            PropertyValue::Date { date, .. } => date.clone(),
+            _ => None,
+        }
+    }
+}
+
+/// created and last edited time
+impl FromPropertyValue for DateTime<Utc> {
+    fn from_property_value(value: &PropertyValue) -> Option<Self> {
+        match value {
+            PropertyValue::CreatedTime {
+                created_time: time, ..
+            }
+            | PropertyValue::LastEditedTime {
+                last_edited_time: time,
+                ..
+            } => Some(*time),
+            _ => None,
+        }
+    }
+}
+
+/// people
+impl FromPropertyValue for Vec<User> {
+    fn from_property_value(value: &PropertyValue) -> Option<Self> {
+        match value {
+            PropertyValue::People { people, .. } => Some(people.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// created by and last edited by
+impl FromPropertyValue for User {
+    fn from_property_value(value: &PropertyValue) -> Option<Self> {
+        match value {
+            PropertyValue::CreatedBy {
+                created_by: user, ..
+            }
+            | PropertyValue::LastEditedBy {
+                last_edited_by: user,
+                ..
+            } => Some(user.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl FromPropertyValue for Vec<RelationValue> {
+    fn from_property_value(value: &PropertyValue) -> Option<Self> {
+        match value {
+            PropertyValue::Relation { relation, .. } => relation.clone(),
+            _ => None,
+        }
+    }
+}
+
+impl FromPropertyValue for Vec<FileReference> {
+    fn from_property_value(value: &PropertyValue) -> Option<Self> {
+        match value {
+            PropertyValue::Files { files, .. } => files.clone(),
+            _ => None,
+        }
+    }
+}
+
+impl FromPropertyValue for FormulaResultValue {
+    fn from_property_value(value: &PropertyValue) -> Option<Self> {
+        match value {
+            PropertyValue::Formula { formula, .. } => Some(formula.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl FromPropertyValue for RollupValue {
+    fn from_property_value(value: &PropertyValue) -> Option<Self> {
+        match value {
+            PropertyValue::Rollup { rollup, .. } => rollup.clone(),
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,360 @@
+//!
+//! # Property Schema
+//!
+//! The configuration of database properties sent to [`databases_create`](crate::NotionApi::databases_create)
+//! and [`databases_update`](crate::NotionApi::databases_update),
+//! as opposed to [`PropertyConfiguration`] which Notion answers with.
+//!
+//! ## Examples
+//! ```rust
+//! use notion_sdk::common::parent::Parent;
+//! use notion_sdk::database::number::NumberFormat;
+//! use notion_sdk::database::schema::PropertySchema;
+//! use notion_sdk::database::{CreateDatabase, UpdateDatabase};
+//! use std::collections::HashMap;
+//! let database = CreateDatabase {
+//!     parent: Parent::Workspace,
+//!     title: vec![],
+//!     properties: HashMap::from([
+//!         ("Name".to_string(), PropertySchema::title()),
+//!         ("Estimate".to_string(), PropertySchema::number(NumberFormat::Number)),
+//!         ("Priority".to_string(), PropertySchema::select(["High", "Low"])),
+//!     ]),
+//! };
+//! let update = UpdateDatabase {
+//!     title: None,
+//!     properties: HashMap::from([
+//!         // rename a property
+//!         ("Estimate".to_string(), Some(PropertySchema::rename("Points"))),
+//!         // delete a property
+//!         ("Priority".to_string(), None),
+//!     ]),
+//! };
+//! ```
+use crate::block::EmptyObject;
+use crate::database::formula::Formula;
+use crate::database::id::{DatabaseId, PropertyId};
+use crate::database::number::{NumberDetails, NumberFormat};
+use crate::database::properties::PropertyConfiguration;
+use crate::database::rollup::RollupFunction;
+use crate::database::Color;
+use serde::{Deserialize, Serialize};
+
+/// A property to create, change or rename.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct PropertySchema {
+    /// New name of the property, used by updates to rename it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Type and configuration, `None` keeps the current one when updating.
+    #[serde(flatten)]
+    pub kind: Option<PropertySchemaKind>,
+}
+
+/// Type of a property and its configuration.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum PropertySchemaKind {
+    Title(EmptyObject),
+    #[serde(rename = "rich_text")]
+    Text(EmptyObject),
+    Number(NumberDetails),
+    Select(SelectSchema),
+    MultiSelect(SelectSchema),
+    Date(EmptyObject),
+    People(EmptyObject),
+    Files(EmptyObject),
+    Checkbox(EmptyObject),
+    Url(EmptyObject),
+    Email(EmptyObject),
+    PhoneNumber(EmptyObject),
+    Formula(Formula),
+    Relation(RelationSchema),
+    Rollup(RollupSchema),
+    CreatedTime(EmptyObject),
+    CreatedBy(EmptyObject),
+    LastEditedTime(EmptyObject),
+    LastEditedBy(EmptyObject),
+}
+
+/// Options of a select or multi-select property.
+/// Updating the options replaces them, options left out are removed.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+pub struct SelectSchema {
+    pub options: Vec<SelectOptionSchema>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct SelectOptionSchema {
+    pub name: String,
+    /// Picked by Notion if left out.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<Color>,
+}
+
+/// Relation to the pages of another database.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct RelationSchema {
+    pub database_id: DatabaseId,
+    #[serde(flatten)]
+    pub kind: RelationKind,
+}
+
+/// Whether the related database shows the relation too.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RelationKind {
+    /// Only this database has the property.
+    SingleProperty { single_property: EmptyObject },
+    /// The related database gets a synced property as well.
+    DualProperty { dual_property: DualProperty },
+}
+
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Default)]
+pub struct DualProperty {
+    /// Name of the synced property in the related database, picked by Notion if left out.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub synced_property_name: Option<String>,
+}
+
+/// Roll up a property of the pages a relation property links to.
+/// Either the name or the id of each property is needed.
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
+pub struct RollupSchema {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relation_property_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relation_property_id: Option<PropertyId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rollup_property_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rollup_property_id: Option<PropertyId>,
+    pub function: RollupFunction,
+}
+
+impl From<PropertySchemaKind> for PropertySchema {
+    fn from(kind: PropertySchemaKind) -> Self {
+        PropertySchema {
+            name: None,
+            kind: Some(kind),
+        }
+    }
+}
+
+impl PropertySchema {
+    /// Only rename a property, keeping its type and configuration.
+    pub fn rename<T: Into<String>>(name: T) -> Self {
+        PropertySchema {
+            name: Some(name.into()),
+            kind: None,
+        }
+    }
+    /// Set the new name of the property.
+    pub fn named<T: Into<String>>(mut self, name: T) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+    pub fn title() -> Self {
+        PropertySchemaKind::Title(EmptyObject {}).into()
+    }
+    pub fn rich_text() -> Self {
+        PropertySchemaKind::Text(EmptyObject {}).into()
+    }
+    pub fn number(format: NumberFormat) -> Self {
+        PropertySchemaKind::Number(NumberDetails { format }).into()
+    }
+    /// select with the given option names, colors are picked by Notion
+    pub fn select<I: IntoIterator<Item = T>, T: Into<String>>(options: I) -> Self {
+        PropertySchemaKind::Select(SelectSchema::from_names(options)).into()
+    }
+    /// multi-select with the given option names, colors are picked by Notion
+    pub fn multi_select<I: IntoIterator<Item = T>, T: Into<String>>(options: I) -> Self {
+        PropertySchemaKind::MultiSelect(SelectSchema::from_names(options)).into()
+    }
+    pub fn date() -> Self {
+        PropertySchemaKind::Date(EmptyObject {}).into()
+    }
+    pub fn people() -> Self {
+        PropertySchemaKind::People(EmptyObject {}).into()
+    }
+    pub fn files() -> Self {
+        PropertySchemaKind::Files(EmptyObject {}).into()
+    }
+    pub fn checkbox() -> Self {
+        PropertySchemaKind::Checkbox(EmptyObject {}).into()
+    }
+    pub fn url() -> Self {
+        PropertySchemaKind::Url(EmptyObject {}).into()
+    }
+    pub fn email() -> Self {
+        PropertySchemaKind::Email(EmptyObject {}).into()
+    }
+    pub fn phone_number() -> Self {
+        PropertySchemaKind::PhoneNumber(EmptyObject {}).into()
+    }
+    pub fn formula<T: Into<String>>(expression: T) -> Self {
+        PropertySchemaKind::Formula(Formula {
+            expression: expression.into(),
+        })
+        .into()
+    }
+    /// relation only shown in this database
+    pub fn relation(database_id: DatabaseId) -> Self {
+        PropertySchemaKind::Relation(RelationSchema {
+            database_id,
+            kind: RelationKind::SingleProperty {
+                single_property: EmptyObject {},
+            },
+        })
+        .into()
+    }
+    /// relation with a synced property in the related database
+    pub fn dual_relation(database_id: DatabaseId, synced_property_name: Option<String>) -> Self {
+        PropertySchemaKind::Relation(RelationSchema {
+            database_id,
+            kind: RelationKind::DualProperty {
+                dual_property: DualProperty {
+                    synced_property_name,
+                },
+            },
+        })
+        .into()
+    }
+    /// roll up `rollup_property` of the pages linked by the `relation_property` of this database
+    pub fn rollup<R: Into<String>, P: Into<String>>(
+        relation_property: R,
+        rollup_property: P,
+        function: RollupFunction,
+    ) -> Self {
+        PropertySchemaKind::Rollup(RollupSchema {
+            relation_property_name: Some(relation_property.into()),
+            relation_property_id: None,
+            rollup_property_name: Some(rollup_property.into()),
+            rollup_property_id: None,
+            function,
+        })
+        .into()
+    }
+    pub fn created_time() -> Self {
+        PropertySchemaKind::CreatedTime(EmptyObject {}).into()
+    }
+    pub fn created_by() -> Self {
+        PropertySchemaKind::CreatedBy(EmptyObject {}).into()
+    }
+    pub fn last_edited_time() -> Self {
+        PropertySchemaKind::LastEditedTime(EmptyObject {}).into()
+    }
+    pub fn last_edited_by() -> Self {
+        PropertySchemaKind::LastEditedBy(EmptyObject {}).into()
+    }
+}
+
+impl PropertySchemaKind {
+    /// type of the property as named by the Notion API
+    pub fn type_name(&self) -> &'static str {
+        use PropertySchemaKind::*;
+        match self {
+            Title(_) => "title",
+            Text(_) => "rich_text",
+            Number(_) => "number",
+            Select(_) => "select",
+            MultiSelect(_) => "multi_select",
+            Date(_) => "date",
+            People(_) => "people",
+            Files(_) => "files",
+            Checkbox(_) => "checkbox",
+            Url(_) => "url",
+            Email(_) => "email",
+            PhoneNumber(_) => "phone_number",
+            Formula(_) => "formula",
+            Relation(_) => "relation",
+            Rollup(_) => "rollup",
+            CreatedTime(_) => "created_time",
+            CreatedBy(_) => "created_by",
+            LastEditedTime(_) => "last_edited_time",
+            LastEditedBy(_) => "last_edited_by",
+        }
+    }
+}
+
+impl SelectSchema {
+    fn from_names<I: IntoIterator<Item = T>, T: Into<String>>(names: I) -> Self {
+        SelectSchema {
+            options: names
+                .into_iter()
+                .map(|name| SelectOptionSchema {
+                    name: name.into(),
+                    color: None,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl PropertyConfiguration {
+    /// The schema recreating this property, `None` for status properties
+    /// which can not be created through the API.
+    pub fn to_schema(&self) -> Option<PropertySchema> {
+        use PropertyConfiguration::*;
+        let empty = EmptyObject {};
+        let kind = match self {
+            Title { .. } => PropertySchemaKind::Title(empty),
+            Text { .. } => PropertySchemaKind::Text(empty),
+            Number { number, .. } => PropertySchemaKind::Number(number.clone()),
+            Select { select, .. } => PropertySchemaKind::Select(select.into()),
+            MultiSelect { multi_select, .. } => {
+                PropertySchemaKind::MultiSelect(multi_select.into())
+            }
+            Status { .. } => return None,
+            Date { .. } => PropertySchemaKind::Date(empty),
+            People { .. } => PropertySchemaKind::People(empty),
+            Files { .. } => PropertySchemaKind::Files(empty),
+            Checkbox { .. } => PropertySchemaKind::Checkbox(empty),
+            Url { .. } => PropertySchemaKind::Url(empty),
+            Email { .. } => PropertySchemaKind::Email(empty),
+            PhoneNumber { .. } => PropertySchemaKind::PhoneNumber(empty),
+            Formula { formula, .. } => PropertySchemaKind::Formula(formula.clone()),
+            Relation { relation, .. } => PropertySchemaKind::Relation(RelationSchema {
+                database_id: relation.database_id.clone(),
+                kind: match &relation.synced_property_name {
+                    Some(name) => RelationKind::DualProperty {
+                        dual_property: DualProperty {
+                            synced_property_name: Some(name.clone()),
+                        },
+                    },
+                    None => RelationKind::SingleProperty {
+                        single_property: empty,
+                    },
+                },
+            }),
+            Rollup { rollup, .. } => PropertySchemaKind::Rollup(RollupSchema {
+                relation_property_name: Some(rollup.relation_property_name.clone()),
+                relation_property_id: None,
+                rollup_property_name: Some(rollup.rollup_property_name.clone()),
+                rollup_property_id: None,
+                function: rollup.function,
+            }),
+            CreatedTime { .. } => PropertySchemaKind::CreatedTime(empty),
+            CreatedBy { .. } => PropertySchemaKind::CreatedBy(empty),
+            LastEditedTime { .. } => PropertySchemaKind::LastEditedTime(empty),
+            LastEditedBy { .. } => PropertySchemaKind::LastEditedBy(empty),
+        };
+        Some(kind.into())
+    }
+}
+
+impl From<&crate::database::select::Select> for SelectSchema {
+    /// keep the names and colors of existing options
+    fn from(select: &crate::database::select::Select) -> Self {
+        SelectSchema {
+            options: select
+                .options
+                .iter()
+                .map(|option| SelectOptionSchema {
+                    name: option.name.clone(),
+                    color: Some(option.color),
+                })
+                .collect(),
+        }
+    }
+}
@@ -5,6 +5,12 @@ use serde::{Deserialize, Serialize};
 #[serde(transparent)]
 pub struct SelectOptionId(String);
 
+impl std::fmt::Display for SelectOptionId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone)]
 pub struct SelectedValue {
     #[serde(skip_serializing_if = "Option::is_none")]
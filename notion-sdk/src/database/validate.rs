@@ -0,0 +1,229 @@
+//!
+//! # Property Validation
+//!
+//! Check the properties of a [`CreatePage`](crate::pages::CreatePage) or [`UpdatePage`](crate::pages::UpdatePage)
+//! against the schema of their database before sending them,
+//! instead of waiting for Notion to answer with a `validation_error` about the first problem.
+//!
+//! ## Examples
+//! ```rust,no_run
+//! use notion_sdk::database::properties::Properties;
+//! use notion_sdk::NotionApi;
+//! # async fn run(properties: Properties) -> Result<(), notion_sdk::error::Error> {
+//! let notion = NotionApi::new("token")?;
+//! let database_id: notion_sdk::database::id::DatabaseId = "3f8d5d7e-6c0a-4d9b-9a51-9f3c2b1e8a10".parse()?;
+//! let database = notion.databases_retrieve(database_id).await?;
+//! for issue in database.check_properties(&properties) {
+//!     eprintln!("{issue}");
+//! }
+//! database.validate_properties(&properties)?;
+//! # Ok(())
+//! # }
+//! ```
+use crate::database::properties::{Properties, PropertyConfiguration, PropertyValue};
+use crate::database::select::SelectedValue;
+use crate::database::Database;
+use crate::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// A property Notion would reject.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum PropertyIssue {
+    /// The database has no property with this name or id.
+    Unknown { name: String },
+    /// The value is of another type than the property.
+    TypeMismatch {
+        name: String,
+        expected: &'static str,
+        found: &'static str,
+    },
+    /// The select, multi-select or status option is not one of the property's options.
+    UnknownOption { name: String, option: String },
+    /// Formulas, rollups and the created/last edited properties are computed by Notion,
+    /// files can not be written back.
+    ReadOnly { name: String, kind: &'static str },
+}
+
+impl Display for PropertyIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PropertyIssue::Unknown { name } => write!(f, "{name}: no such property"),
+            PropertyIssue::TypeMismatch {
+                name,
+                expected,
+                found,
+            } => write!(f, "{name}: expected a {expected} value, found {found}"),
+            PropertyIssue::UnknownOption { name, option } => {
+                write!(f, "{name}: no option named {option}")
+            }
+            PropertyIssue::ReadOnly { name, kind } => {
+                write!(f, "{name}: {kind} properties can not be written")
+            }
+        }
+    }
+}
+
+impl Database {
+    /// Find the property a payload key refers to, by name or by id.
+    pub fn find_property(&self, key: &str) -> Option<&PropertyConfiguration> {
+        self.properties.get(key).or_else(|| {
+            self.properties
+                .values()
+                .find(|property| property.id().0 == key)
+        })
+    }
+
+    /// Every problem with the properties, sorted by property name.
+    pub fn check_properties(&self, properties: &Properties) -> Vec<PropertyIssue> {
+        let mut names: Vec<&String> = properties.properties.keys().collect();
+        names.sort();
+        let mut issues = vec![];
+        for name in names {
+            let value = &properties.properties[name];
+            let Some(property) = self.find_property(name) else {
+                issues.push(PropertyIssue::Unknown { name: name.clone() });
+                continue;
+            };
+            if property.is_read_only() {
+                issues.push(PropertyIssue::ReadOnly {
+                    name: name.clone(),
+                    kind: property.type_name(),
+                });
+            } else if property.type_name() != value.type_name() {
+                issues.push(PropertyIssue::TypeMismatch {
+                    name: name.clone(),
+                    expected: property.type_name(),
+                    found: value.type_name(),
+                });
+            } else if let Some(options) = property.options() {
+                for selected in selected_options(value) {
+                    let known = options.iter().any(|option| {
+                        selected.id.as_ref().is_some_and(|id| *id == option.id)
+                            || selected.name.as_ref().is_some_and(|n| *n == option.name)
+                    });
+                    if !known {
+                        let option = selected
+                            .name
+                            .clone()
+                            .or_else(|| selected.id.as_ref().map(|id| id.to_string()))
+                            .unwrap_or_default();
+                        issues.push(PropertyIssue::UnknownOption {
+                            name: name.clone(),
+                            option,
+                        });
+                    }
+                }
+            }
+        }
+        issues
+    }
+
+    /// Fail with [`Error::InvalidProperties`] if Notion would reject the properties.
+    pub fn validate_properties(&self, properties: &Properties) -> Result<(), Error> {
+        let issues = self.check_properties(properties);
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::InvalidProperties { issues })
+        }
+    }
+}
+
+fn selected_options(value: &PropertyValue) -> &[SelectedValue] {
+    match value {
+        PropertyValue::Select { select: option, .. }
+        | PropertyValue::Status { status: option, .. } => option.as_slice(),
+        PropertyValue::MultiSelect { multi_select, .. } => {
+            multi_select.as_deref().unwrap_or_default()
+        }
+        _ => &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::database;
+    use serde_json::{json, Value};
+
+    fn tasks() -> Database {
+        database(json!({
+            "Name": {"id": "title", "name": "Name", "type": "title", "title": {}},
+            "Estimate": {"id": "a", "name": "Estimate", "type": "number", "number": {"format": "number"}},
+            "Priority": {"id": "b", "name": "Priority", "type": "select", "select": {"options": [
+                {"id": "1", "name": "Low", "color": "default"},
+                {"id": "2", "name": "High", "color": "red"},
+            ]}},
+            "Created": {"id": "c", "name": "Created", "type": "created_time", "created_time": {}},
+        }))
+    }
+
+    fn properties(properties: Value) -> Properties {
+        serde_json::from_value(properties).unwrap()
+    }
+
+    #[test]
+    fn known_properties_are_valid() {
+        let properties = properties(json!({
+            "Estimate": {"type": "number", "number": 3},
+            "b": {"type": "select", "select": {"name": "High", "color": "red"}},
+        }));
+        assert_eq!(tasks().check_properties(&properties), vec![]);
+        assert!(tasks().validate_properties(&properties).is_ok());
+    }
+
+    #[test]
+    fn unknown_properties_are_reported() {
+        let properties = properties(json!({"Owner": {"type": "checkbox", "checkbox": true}}));
+        assert_eq!(
+            tasks().check_properties(&properties),
+            vec![PropertyIssue::Unknown {
+                name: "Owner".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn values_of_another_type_are_reported() {
+        let properties = properties(json!({"Estimate": {"type": "checkbox", "checkbox": true}}));
+        assert_eq!(
+            tasks().check_properties(&properties),
+            vec![PropertyIssue::TypeMismatch {
+                name: "Estimate".to_string(),
+                expected: "number",
+                found: "checkbox",
+            }]
+        );
+    }
+
+    #[test]
+    fn options_missing_from_the_schema_are_reported() {
+        let properties = properties(json!({
+            "Priority": {"type": "select", "select": {"name": "Urgent", "color": "default"}},
+        }));
+        assert_eq!(
+            tasks().check_properties(&properties),
+            vec![PropertyIssue::UnknownOption {
+                name: "Priority".to_string(),
+                option: "Urgent".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn read_only_properties_are_reported() {
+        let properties = properties(json!({
+            "Created": {"type": "created_time", "created_time": "2026-10-01T00:00:00Z"},
+            "Name": {"type": "title", "title": []},
+        }));
+        let issues = vec![PropertyIssue::ReadOnly {
+            name: "Created".to_string(),
+            kind: "created_time",
+        }];
+        assert_eq!(tasks().check_properties(&properties), issues);
+        match tasks().validate_properties(&properties) {
+            Err(Error::InvalidProperties { issues: found }) => assert_eq!(found, issues),
+            result => panic!("expected invalid properties, got {result:?}"),
+        }
+    }
+}
@@ -1,6 +1,8 @@
 //!
 //! # Notion Error
 //!
+use crate::block::Block;
+use crate::database::validate::PropertyIssue;
 use crate::pagination::Object;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Debug, Display, Formatter};
@@ -17,6 +19,12 @@ pub enum Error {
         source: reqwest::header::InvalidHeaderValue,
     },
 
+    #[error("Invalid {} header value: {}", header, source)]
+    InvalidHeaderValue {
+        header: &'static str,
+        source: reqwest::header::InvalidHeaderValue,
+    },
+
     #[error("Unable to build reqwest HTTP client: {}", source)]
     ErrorBuildingClient { source: reqwest::Error },
 
@@ -32,13 +40,52 @@ pub enum Error {
     #[error("Error parsing json response: {}", source)]
     JsonParseError { source: serde_json::Error },
 
+    #[error("Block of type {} can not be created through the API", block_type)]
+    UnsupportedBlock { block_type: &'static str },
+
+    #[error("Block of type {} uses a file hosted by Notion, only external files can be used to create it", block_type)]
+    HostedFile { block_type: &'static str },
+
+    #[error("Page property {} is missing, empty or not of type {}", name, kind)]
+    InvalidPageProperty { name: String, kind: &'static str },
+
+    #[error("Invalid page properties: {}", .issues.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+    InvalidProperties { issues: Vec<PropertyIssue> },
+
+    #[error("Appending block children failed after {} of them were appended: {}", .appended.len(), source)]
+    PartiallyAppended {
+        /// blocks created by the requests which succeeded, resume after the last one
+        appended: Vec<Block>,
+        source: Box<Error>,
+    },
+
     #[error("Unexpected API Response")]
-    UnexpectedResponse { response: Object },
+    UnexpectedResponse { response: Box<Object> },
 
     #[error("API Error {}({}): {}", .error.code, .error.status, .error.message)]
     ApiError { error: ErrorResponse },
 }
 
+impl Error {
+    /// Whether sending the same request again later may succeed, and Notion did not act on it.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::ApiError { error } => error.code.is_retryable(),
+            Error::RequestFailed { source } => source.is_connect(),
+            _ => false,
+        }
+    }
+    /// Whether sending the same request again later may succeed,
+    /// but Notion may already have applied it, like after a timeout.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Error::ApiError { error } => error.code.is_transient(),
+            Error::RequestFailed { source } => source.is_timeout(),
+            _ => false,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(transparent)]
 pub struct StatusCode(NonZeroU16);
@@ -91,6 +138,25 @@ pub enum ErrorCode {
     Unknown,
 }
 
+impl ErrorCode {
+    /// Errors caused by throttling or an unavailable database, the request was not applied
+    /// and can be retried as is.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            ErrorCode::RateLimited | ErrorCode::DatabaseConnectionUnavailable
+        )
+    }
+    /// Errors which may go away on their own, but the request may have been applied,
+    /// only idempotent requests can be retried.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            ErrorCode::ServiceUnavailable | ErrorCode::ConflictError
+        )
+    }
+}
+
 impl Display for ErrorCode {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{self:?}")
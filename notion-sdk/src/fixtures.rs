@@ -0,0 +1,251 @@
+//! Objects as Notion returns them, for tests.
+use crate::block::{Block, NewBlock};
+use crate::database::Database;
+use crate::pages::Page;
+use crate::NotionApi;
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use wiremock::matchers::path_regex;
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+pub(crate) const DATABASE_ID: &str = "3f8d5d7e-6c0a-4d9b-9a51-9f3c2b1e8a10";
+
+fn user() -> Value {
+    json!({"object": "user", "id": "c2f20311-9e54-4d11-8c79-7398424ae41e"})
+}
+
+/// A database in the workspace with the given property schema.
+pub(crate) fn database(properties: Value) -> Database {
+    serde_json::from_value(json!({
+        "id": DATABASE_ID,
+        "icon": null,
+        "created_time": "2026-10-01T00:00:00Z",
+        "last_edited_time": "2026-10-01T00:00:00Z",
+        "created_by": user(),
+        "last_edited_by": user(),
+        "title": [],
+        "properties": properties,
+        "archived": false,
+        "is_inline": false,
+        "description": [],
+        "url": "https://www.notion.so/3f8d5d7e6c0a4d9b9a519f3c2b1e8a10",
+        "parent": {"type": "workspace", "workspace": true},
+    }))
+    .unwrap()
+}
+
+/// Page number `n` of the database, with the given property values.
+pub(crate) fn page(n: u8, properties: Value) -> Page {
+    serde_json::from_value(json!({
+        "id": format!("00000000-0000-0000-0000-{n:012}"),
+        "icon": null,
+        "created_time": "2026-10-01T00:00:00Z",
+        "created_by": user(),
+        "last_edited_time": "2026-10-01T00:00:00Z",
+        "last_edited_by": user(),
+        "archived": false,
+        "properties": properties,
+        "parent": {"type": "database_id", "database_id": DATABASE_ID},
+    }))
+    .unwrap()
+}
+
+/// Midnight UTC of a day like `2026-10-01`.
+pub(crate) fn day(day: &str) -> DateTime<Utc> {
+    format!("{day}T00:00:00Z").parse().unwrap()
+}
+
+/// A block as Notion returns it, with the fields only fetched blocks have.
+pub(crate) fn fetched(block: NewBlock) -> Block {
+    fn add_common(value: &mut Value) {
+        match value {
+            Value::Object(object) => {
+                let is_block = !object.contains_key("plain_text")
+                    && object
+                        .get("type")
+                        .and_then(Value::as_str)
+                        .is_some_and(|kind| object.contains_key(kind));
+                object.values_mut().for_each(add_common);
+                if is_block {
+                    let user =
+                        json!({"object": "user", "id": "c2f20311-9e54-4d11-8c79-7398424ae41e"});
+                    object.insert("id".into(), json!("0c1b0b7e-1a4b-4d3a-a2a5-07c2a4d0f0d1"));
+                    object.insert("created_time".into(), json!("2026-10-01T00:00:00Z"));
+                    object.insert("last_edited_time".into(), json!("2026-10-01T00:00:00Z"));
+                    object.insert("has_children".into(), json!(false));
+                    object.insert("created_by".into(), user.clone());
+                    object.insert("last_edited_by".into(), user);
+                }
+            }
+            Value::Array(values) => values.iter_mut().for_each(add_common),
+            _ => {}
+        }
+    }
+    let mut value = serde_json::to_value(block).unwrap();
+    add_common(&mut value);
+    serde_json::from_value(value).unwrap()
+}
+
+/// The block endpoints of Notion, serving an in-memory tree of blocks.
+///
+/// Listing children returns pages of `page_size` blocks with the index of the next one as cursor.
+#[derive(Clone)]
+pub(crate) struct FakeBlocks {
+    state: Arc<Mutex<FakeState>>,
+}
+
+struct FakeState {
+    page_size: usize,
+    next_id: u32,
+    children: HashMap<String, Vec<Value>>,
+    /// parent and body of every append request
+    appends: Vec<(String, Value)>,
+    /// number of the append request answered with a `validation_error`
+    failing_append: Option<usize>,
+}
+
+impl FakeBlocks {
+    pub(crate) async fn start(page_size: usize) -> (MockServer, NotionApi, Self) {
+        let server = MockServer::start().await;
+        let fake = FakeBlocks {
+            state: Arc::new(Mutex::new(FakeState {
+                page_size,
+                next_id: 0,
+                children: HashMap::new(),
+                appends: vec![],
+                failing_append: None,
+            })),
+        };
+        Mock::given(path_regex("^/v1/blocks/[^/]+/children$"))
+            .respond_with(fake.clone())
+            .mount(&server)
+            .await;
+        let notion = NotionApi::builder("token")
+            .base_url(format!("{}/v1", server.uri()))
+            .build()
+            .unwrap();
+        (server, notion, fake)
+    }
+
+    /// parent and body of every append request so far
+    pub(crate) fn appends(&self) -> Vec<(String, Value)> {
+        self.state.lock().unwrap().appends.clone()
+    }
+
+    /// answer append request number `n`, counting from zero, with a `validation_error`
+    pub(crate) fn fail_append(&self, n: usize) {
+        self.state.lock().unwrap().failing_append = Some(n);
+    }
+
+    /// ids of the children of a block, in order
+    pub(crate) fn child_ids(&self, id: &str) -> Vec<String> {
+        let state = self.state.lock().unwrap();
+        let children = state.children.get(id).cloned().unwrap_or_default();
+        children
+            .iter()
+            .map(|block| block["id"].as_str().unwrap_or_default().to_string())
+            .collect()
+    }
+}
+
+impl FakeState {
+    /// create a block with its children, the way Notion returns it
+    fn create(&mut self, mut block: Value) -> Value {
+        self.next_id += 1;
+        let id = format!("00000000-0000-4000-8000-{:012}", self.next_id);
+        let kind = block["type"].as_str().unwrap_or_default().to_string();
+        let children = match block.get_mut(&kind).and_then(Value::as_object_mut) {
+            Some(object) => object.remove("children"),
+            None => None,
+        };
+        let children: Vec<Value> = match children {
+            Some(Value::Array(children)) => children,
+            _ => vec![],
+        };
+        let object = block.as_object_mut().unwrap();
+        object.insert("object".into(), json!("block"));
+        object.insert("id".into(), json!(id));
+        object.insert("created_time".into(), json!("2026-10-01T00:00:00Z"));
+        object.insert("last_edited_time".into(), json!("2026-10-01T00:00:00Z"));
+        object.insert("created_by".into(), user());
+        object.insert("last_edited_by".into(), user());
+        object.insert("has_children".into(), json!(!children.is_empty()));
+        object.insert("archived".into(), json!(false));
+        let created: Vec<Value> = children
+            .into_iter()
+            .map(|child| self.create(child))
+            .collect();
+        if !created.is_empty() {
+            self.children.insert(id, created);
+        }
+        block
+    }
+}
+
+impl Respond for FakeBlocks {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let mut state = self.state.lock().unwrap();
+        let parent = request
+            .url
+            .path()
+            .split('/')
+            .nth(3)
+            .unwrap_or_default()
+            .to_string();
+        if request.method.as_str() == "PATCH" {
+            let body: Value = serde_json::from_slice(&request.body).unwrap();
+            state.appends.push((parent.clone(), body.clone()));
+            if state.failing_append == Some(state.appends.len() - 1) {
+                return ResponseTemplate::new(400).set_body_json(json!({
+                    "object": "error",
+                    "status": 400,
+                    "code": "validation_error",
+                    "message": "body failed validation",
+                }));
+            }
+            let created: Vec<Value> = match &body["children"] {
+                Value::Array(children) => children
+                    .iter()
+                    .map(|child| state.create(child.clone()))
+                    .collect(),
+                _ => vec![],
+            };
+            if !created.is_empty() {
+                let block = state
+                    .children
+                    .values_mut()
+                    .flatten()
+                    .find(|b| b["id"] == parent);
+                if let Some(block) = block {
+                    block["has_children"] = json!(true);
+                }
+            }
+            let siblings = state.children.entry(parent).or_default();
+            let position = body["after"]
+                .as_str()
+                .and_then(|after| siblings.iter().position(|block| block["id"] == after))
+                .map_or(siblings.len(), |index| index + 1);
+            siblings.splice(position..position, created.clone());
+            return ResponseTemplate::new(200).set_body_json(
+                json!({"object": "list", "results": created, "next_cursor": null, "has_more": false}),
+            );
+        }
+        let start: usize = request
+            .url
+            .query_pairs()
+            .find(|(key, _)| key == "start_cursor")
+            .and_then(|(_, cursor)| cursor.parse().ok())
+            .unwrap_or(0);
+        let children = state.children.get(&parent).cloned().unwrap_or_default();
+        let end = children.len().min(start + state.page_size);
+        let has_more = end < children.len();
+        ResponseTemplate::new(200).set_body_json(json!({
+            "object": "list",
+            "results": children[start.min(end)..end],
+            "next_cursor": has_more.then(|| end.to_string()),
+            "has_more": has_more,
+        }))
+    }
+}
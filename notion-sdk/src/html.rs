@@ -0,0 +1,608 @@
+//!
+//! # Notion HTML
+//!
+//! Render blocks and rich text as HTML.
+//! Colors become `notion-*` CSS classes, which [`HtmlRenderer::document`] ships a stylesheet for,
+//! column lists become flex layouts and toggles become `<details>` elements.
+//! All text and urls are escaped, links and sources other than `http`, `https`, `mailto`
+//! and relative urls are written as plain text.
+//!
+//! ## Examples
+//! ```rust
+//! use notion_sdk::block::Block;
+//! use notion_sdk::html::HtmlRenderer;
+//! let renderer = HtmlRenderer::new().with_hook(|block: &Block, _: &HtmlRenderer| match block {
+//!     Block::Divider { .. } => Some("<hr class=\"fancy\">".to_string()),
+//!     _ => None,
+//! });
+//! let blocks: Vec<Block> = vec![];
+//! let page = renderer.document("Release notes", &blocks);
+//! assert!(page.starts_with("<!DOCTYPE html>"));
+//! ```
+use crate::block::{Block, CodeLanguage, LinkToPageFields, TableFields};
+use crate::common::file::FileOrEmojiObject;
+use crate::common::rich_text::{RichText, TextColor};
+use uuid::Uuid;
+
+/// Replace the default rendering of some blocks.
+///
+/// Returning `None` falls back to the default, [`HtmlRenderer::render_children`]
+/// renders the children of a block the hook took over.
+pub trait RenderHook {
+    fn render_block(&self, block: &Block, renderer: &HtmlRenderer) -> Option<String>;
+}
+
+impl<F> RenderHook for F
+where
+    F: Fn(&Block, &HtmlRenderer) -> Option<String>,
+{
+    fn render_block(&self, block: &Block, renderer: &HtmlRenderer) -> Option<String> {
+        self(block, renderer)
+    }
+}
+
+/// Renders blocks fetched with [`NotionApi::fetch_block_tree`](crate::NotionApi::fetch_block_tree).
+#[derive(Default)]
+pub struct HtmlRenderer {
+    hook: Option<Box<dyn RenderHook>>,
+}
+
+impl std::fmt::Debug for HtmlRenderer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HtmlRenderer")
+            .field("hook", &self.hook.is_some())
+            .finish()
+    }
+}
+
+/// Render blocks with the default [`HtmlRenderer`].
+pub fn to_html(blocks: &[Block]) -> String {
+    HtmlRenderer::new().render(blocks)
+}
+
+impl HtmlRenderer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask `hook` first for every block.
+    pub fn with_hook<H: RenderHook + 'static>(mut self, hook: H) -> Self {
+        self.hook = Some(Box::new(hook));
+        self
+    }
+
+    /// A complete HTML page with the stylesheet for the `notion-*` classes.
+    pub fn document(&self, title: &str, blocks: &[Block]) -> String {
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{}</style>\n</head>\n<body>\n<article class=\"notion-page\">\n{}</article>\n</body>\n</html>\n",
+            escape_html(title),
+            STYLESHEET,
+            self.render(blocks)
+        )
+    }
+
+    /// Render blocks, consecutive list items are grouped into one list.
+    pub fn render(&self, blocks: &[Block]) -> String {
+        let mut html = String::new();
+        // tag and class of the list being rendered
+        let mut list: Option<(&str, &str)> = None;
+        for block in blocks {
+            let item = match block {
+                Block::BulletedListItem { .. } => Some(("ul", "notion-bulleted-list")),
+                Block::NumberedListItem { .. } => Some(("ol", "notion-numbered-list")),
+                Block::ToDo { .. } => Some(("ul", "notion-to-do-list")),
+                _ => None,
+            };
+            if list != item {
+                if let Some((tag, _)) = list {
+                    html.push_str(&format!("</{tag}>\n"));
+                }
+                if let Some((tag, class)) = item {
+                    html.push_str(&format!("<{tag} class=\"{class}\">\n"));
+                }
+                list = item;
+            }
+            html.push_str(&self.render_block(block));
+            html.push('\n');
+        }
+        if let Some((tag, _)) = list {
+            html.push_str(&format!("</{tag}>\n"));
+        }
+        html
+    }
+
+    /// Render one block and its children, list items are not wrapped in a list.
+    pub fn render_block(&self, block: &Block) -> String {
+        if let Some(html) = self
+            .hook
+            .as_ref()
+            .and_then(|hook| hook.render_block(block, self))
+        {
+            return html;
+        }
+        match block {
+            Block::Paragraph { paragraph, .. } => format!(
+                "<p{}>{}</p>{}",
+                class("notion-paragraph", &paragraph.color),
+                self.render_rich_text(&paragraph.rich_text),
+                self.indented(block)
+            ),
+            Block::Heading1 { heading_1: heading, .. }
+            | Block::Heading2 { heading_2: heading, .. }
+            | Block::Heading3 { heading_3: heading, .. } => {
+                let level = match block {
+                    Block::Heading1 { .. } => 1,
+                    Block::Heading2 { .. } => 2,
+                    _ => 3,
+                };
+                let html = format!(
+                    "<h{level}{}>{}</h{level}>",
+                    class("notion-heading", &heading.color),
+                    self.render_rich_text(&heading.rich_text)
+                );
+                if heading.is_toggleable {
+                    format!(
+                        "<details class=\"notion-toggle\">\n<summary>{html}</summary>\n{}</details>",
+                        self.render_children(block)
+                    )
+                } else {
+                    html
+                }
+            }
+            Block::CallOut { call_out, .. } => format!(
+                "<div{}>\n<div class=\"notion-callout-icon\">{}</div>\n<div class=\"notion-callout-text\">{}\n{}</div>\n</div>",
+                class("notion-callout", &call_out.color),
+                icon(&call_out.icon),
+                self.render_rich_text(&call_out.rich_text),
+                self.render_children(block)
+            ),
+            Block::Quote { quote, .. } => format!(
+                "<blockquote{}>{}\n{}</blockquote>",
+                class("notion-quote", &quote.color),
+                self.render_rich_text(&quote.rich_text),
+                self.render_children(block)
+            ),
+            Block::BulletedListItem {
+                bulleted_list_item: item,
+                ..
+            }
+            | Block::NumberedListItem {
+                numbered_list_item: item,
+                ..
+            } => format!(
+                "<li{}>{}{}</li>",
+                class("notion-list-item", &item.color),
+                self.render_rich_text(&item.rich_text),
+                self.nested(block)
+            ),
+            Block::ToDo { to_do, .. } => format!(
+                "<li{}><input type=\"checkbox\" disabled{}> {}{}</li>",
+                class(
+                    if to_do.checked {
+                        "notion-to-do notion-checked"
+                    } else {
+                        "notion-to-do"
+                    },
+                    &to_do.color
+                ),
+                if to_do.checked { " checked" } else { "" },
+                self.render_rich_text(&to_do.rich_text),
+                self.nested(block)
+            ),
+            Block::Toggle { toggle, .. } => format!(
+                "<details{}>\n<summary>{}</summary>\n{}</details>",
+                class("notion-toggle", &toggle.color),
+                self.render_rich_text(&toggle.rich_text),
+                self.render_children(block)
+            ),
+            Block::Code { code, .. } => {
+                let source: String = code.rich_text.iter().map(RichText::plain_text).collect();
+                let pre = format!(
+                    "<pre class=\"notion-code\"><code class=\"language-{}\">{}</code></pre>",
+                    language_class(&code.language),
+                    escape_html(&source)
+                );
+                figure(pre, self.render_rich_text(&code.caption))
+            }
+            Block::ChildPage { common, child_page } => format!(
+                "<p class=\"notion-child-page\"><a href=\"{}\">{}</a></p>",
+                notion_url(&common.id.0),
+                escape_html(&child_page.title)
+            ),
+            Block::ChildDatabase { common, child_page } => format!(
+                "<p class=\"notion-child-database\"><a href=\"{}\">{}</a></p>",
+                notion_url(&common.id.0),
+                escape_html(&child_page.title)
+            ),
+            Block::Embed { embed, .. } => match safe_url(&embed.url) {
+                Some(url) => format!("<iframe class=\"notion-embed\" src=\"{url}\"></iframe>"),
+                None => file_link("notion-embed", &embed.url),
+            },
+            Block::Image { image, .. } => match safe_url(image.url()) {
+                Some(url) => {
+                    format!("<figure class=\"notion-image\"><img src=\"{url}\" alt=\"\"></figure>")
+                }
+                None => file_link("notion-image", image.url()),
+            },
+            Block::Video { video, .. } => match safe_url(video.url()) {
+                Some(url) => {
+                    format!("<video class=\"notion-video\" src=\"{url}\" controls></video>")
+                }
+                None => file_link("notion-video", video.url()),
+            },
+            Block::File { file, .. } => file_link("notion-file", file.url()),
+            Block::Pdf { pdf, .. } => file_link("notion-pdf", pdf.url()),
+            Block::Bookmark { bookmark, .. } => {
+                let text = if bookmark.caption.is_empty() {
+                    escape_html(&bookmark.url)
+                } else {
+                    self.render_rich_text(&bookmark.caption)
+                };
+                format!(
+                    "<p class=\"notion-bookmark\">{}</p>",
+                    link(&bookmark.url, text)
+                )
+            }
+            Block::LinkPreview { link_preview, .. } => file_link("notion-link-preview", &link_preview.url),
+            Block::Equation { equation, .. } => format!(
+                "<div class=\"notion-equation\">{}</div>",
+                escape_html(&equation.expression)
+            ),
+            Block::Divider { .. } => "<hr class=\"notion-divider\">".to_string(),
+            Block::ColumnList { column_list, .. } => {
+                let columns: String = column_list
+                    .children
+                    .iter()
+                    .map(|column| self.render_block(column))
+                    .collect();
+                format!(
+                    "<div class=\"notion-column-list\" style=\"display: flex; gap: 1em;\">\n{columns}</div>"
+                )
+            }
+            Block::Column { column, .. } => format!(
+                "<div class=\"notion-column\" style=\"flex: 1 1 0; min-width: 0;\">\n{}</div>\n",
+                self.render(&column.children)
+            ),
+            Block::LinkToPage { link_to_page, .. } => {
+                let id = match link_to_page {
+                    LinkToPageFields::PageId { page_id } => &page_id.0,
+                    LinkToPageFields::DatabaseId { database_id } => &database_id.0,
+                };
+                format!(
+                    "<p class=\"notion-link-to-page\"><a href=\"{0}\">{0}</a></p>",
+                    notion_url(id)
+                )
+            }
+            Block::Table { table, .. } => self.table(table),
+            Block::TableRow { table_row, .. } => {
+                let cells: String = table_row
+                    .cells
+                    .iter()
+                    .map(|cell| format!("<td>{}</td>", self.render_rich_text(cell)))
+                    .collect();
+                format!("<tr>{cells}</tr>")
+            }
+            Block::SyncedBlock { synced_block, .. } => format!(
+                "<div class=\"notion-synced-block\">\n{}</div>",
+                self.render(&synced_block.children)
+            ),
+            Block::TableOfContents { .. }
+            | Block::Breadcrumb { .. }
+            | Block::Template { .. }
+            | Block::Unsupported { .. }
+            | Block::Unknown => format!("<!-- unsupported block: {} -->", block.block_type()),
+        }
+    }
+
+    /// Render the children of a block, empty if it has none.
+    pub fn render_children(&self, block: &Block) -> String {
+        block
+            .children()
+            .map(|children| self.render(children))
+            .unwrap_or_default()
+    }
+
+    /// Render rich text as inline HTML.
+    pub fn render_rich_text(&self, rich_text: &[RichText]) -> String {
+        rich_text.iter().map(span_to_html).collect()
+    }
+
+    /// children of a paragraph are indented below it
+    fn indented(&self, block: &Block) -> String {
+        match block.children().filter(|c| !c.is_empty()) {
+            Some(children) => format!(
+                "\n<div class=\"notion-indent\">\n{}</div>",
+                self.render(children)
+            ),
+            None => String::new(),
+        }
+    }
+
+    /// children of a list item are rendered inside it
+    fn nested(&self, block: &Block) -> String {
+        match block.children().filter(|c| !c.is_empty()) {
+            Some(children) => format!("\n{}", self.render(children)),
+            None => String::new(),
+        }
+    }
+
+    fn table(&self, table: &TableFields) -> String {
+        let mut html = String::from("<table class=\"notion-table\">\n");
+        for (index, row) in table.children.iter().enumerate() {
+            let Block::TableRow { table_row, .. } = row else {
+                continue;
+            };
+            let header_row = index == 0 && table.has_column_header;
+            if header_row {
+                html.push_str("<thead>\n");
+            } else if index == 0 || (index == 1 && table.has_column_header) {
+                html.push_str("<tbody>\n");
+            }
+            html.push_str("<tr>");
+            for (column, cell) in table_row.cells.iter().enumerate() {
+                let tag = if header_row || (column == 0 && table.has_row_header) {
+                    "th"
+                } else {
+                    "td"
+                };
+                html.push_str(&format!("<{tag}>{}</{tag}>", self.render_rich_text(cell)));
+            }
+            html.push_str("</tr>\n");
+            if header_row {
+                html.push_str("</thead>\n");
+            }
+        }
+        let rows = table.children.len();
+        if rows > usize::from(table.has_column_header) {
+            html.push_str("</tbody>\n");
+        }
+        html.push_str("</table>");
+        html
+    }
+}
+
+fn span_to_html(span: &RichText) -> String {
+    let common = span.common();
+    let mut html = match span {
+        RichText::Equation { equation, .. } => format!(
+            "<span class=\"notion-equation\">{}</span>",
+            escape_html(&equation.expression)
+        ),
+        _ => escape_html(&common.plain_text).replace('\n', "<br>"),
+    };
+    if let Some(annotations) = &common.annotations {
+        if annotations.code == Some(true) {
+            html = format!("<code>{html}</code>");
+        }
+        if annotations.bold == Some(true) {
+            html = format!("<strong>{html}</strong>");
+        }
+        if annotations.italic == Some(true) {
+            html = format!("<em>{html}</em>");
+        }
+        if annotations.strikethrough == Some(true) {
+            html = format!("<s>{html}</s>");
+        }
+        if annotations.underline == Some(true) {
+            html = format!("<u>{html}</u>");
+        }
+        if let Some(class) = annotations.color.as_ref().and_then(color_class) {
+            html = format!("<span class=\"{class}\">{html}</span>");
+        }
+    }
+    let href = match span {
+        RichText::Text { text, .. } => text
+            .link
+            .as_ref()
+            .map(|link| link.url.as_str())
+            .or(common.href.as_deref()),
+        _ => common.href.as_deref(),
+    };
+    match href {
+        Some(href) => link(href, html),
+        None => html,
+    }
+}
+
+/// `class` attribute with the color class appended
+fn class(name: &str, color: &TextColor) -> String {
+    match color_class(color) {
+        Some(color) => format!(" class=\"{name} {color}\""),
+        None => format!(" class=\"{name}\""),
+    }
+}
+
+/// `notion-red`, `notion-red-background`, nothing for the default color
+fn color_class(color: &TextColor) -> Option<String> {
+    if *color == TextColor::Default {
+        return None;
+    }
+    let name = serde_json::to_value(color).ok()?;
+    Some(format!("notion-{}", name.as_str()?.replace('_', "-")))
+}
+
+fn icon(icon: &FileOrEmojiObject) -> String {
+    match icon {
+        FileOrEmojiObject::Emoji { emoji } => escape_html(emoji),
+        FileOrEmojiObject::File { file } => img(&file.url),
+        FileOrEmojiObject::External { external } => img(&external.url),
+    }
+}
+
+fn figure(html: String, caption: String) -> String {
+    if caption.is_empty() {
+        html
+    } else {
+        format!("<figure>{html}<figcaption>{caption}</figcaption></figure>")
+    }
+}
+
+fn file_link(class: &str, url: &str) -> String {
+    format!("<p class=\"{class}\">{}</p>", link(url, escape_html(url)))
+}
+
+fn img(url: &str) -> String {
+    match safe_url(url) {
+        Some(url) => format!("<img src=\"{url}\" alt=\"\">"),
+        None => String::new(),
+    }
+}
+
+/// `html` linked to `url`, or left as it is when the url is not safe to follow
+fn link(url: &str, html: String) -> String {
+    match safe_url(url) {
+        Some(url) => format!("<a href=\"{url}\">{html}</a>"),
+        None => html,
+    }
+}
+
+/// Escaped url for an attribute, `None` unless it is relative or an `http`, `https` or `mailto` url.
+fn safe_url(url: &str) -> Option<String> {
+    // browsers drop these around and inside a scheme, `java\tscript:` is still `javascript:`
+    let ignored = |c: char| c.is_ascii_whitespace() || c.is_ascii_control();
+    let url = url.trim_matches(ignored);
+    if let Some((scheme, _)) = url.split_once(':') {
+        // a colon after a path, query or fragment starts no scheme
+        if !scheme.contains(['/', '?', '#']) {
+            let scheme: String = scheme.chars().filter(|c| !ignored(*c)).collect();
+            if !["http", "https", "mailto"]
+                .iter()
+                .any(|allowed| scheme.eq_ignore_ascii_case(allowed))
+            {
+                return None;
+            }
+        }
+    }
+    Some(escape_html(url))
+}
+
+/// highlight.js and Prism class names, without characters a class can not hold
+fn language_class(language: &CodeLanguage) -> String {
+    match language {
+        CodeLanguage::PlainText => "plaintext".to_string(),
+        CodeLanguage::CPlusPlus => "cpp".to_string(),
+        CodeLanguage::CSharp => "csharp".to_string(),
+        CodeLanguage::FSharp => "fsharp".to_string(),
+        CodeLanguage::ObjectiveC => "objectivec".to_string(),
+        CodeLanguage::VbNet => "vbnet".to_string(),
+        CodeLanguage::VisualBasic => "vb".to_string(),
+        CodeLanguage::JavaCAndCPlusPlusAndCSharp => "java".to_string(),
+        language => serde_json::to_value(language)
+            .ok()
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_default(),
+    }
+}
+
+fn notion_url(id: &Uuid) -> String {
+    format!("https://www.notion.so/{}", id.simple())
+}
+
+/// Escape text for use in HTML content and quoted attribute values.
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Notion's palette for the `notion-*` color classes,
+/// the block rules come first so that color classes override their background
+const STYLESHEET: &str = ".notion-callout { display: flex; gap: 0.5em; padding: 1em; border-radius: 4px; background: #f1f1ef; }
+.notion-gray { color: #787774; }
+.notion-brown { color: #9f6b53; }
+.notion-orange { color: #d9730d; }
+.notion-yellow { color: #cb912f; }
+.notion-green { color: #448361; }
+.notion-blue { color: #337ea9; }
+.notion-purple { color: #9065b0; }
+.notion-pink { color: #c14c8a; }
+.notion-red { color: #d44c47; }
+.notion-gray-background { background: #f1f1ef; }
+.notion-brown-background { background: #f4eeee; }
+.notion-orange-background { background: #fbecdd; }
+.notion-yellow-background { background: #fbf3db; }
+.notion-green-background { background: #edf3ec; }
+.notion-blue-background { background: #e7f3f8; }
+.notion-purple-background { background: #f6f3f9; }
+.notion-pink-background { background: #faf1f5; }
+.notion-red-background { background: #fdebec; }
+.notion-indent { margin-left: 1.5em; }
+.notion-to-do-list { list-style: none; padding-left: 0; }
+.notion-checked { text-decoration: line-through; opacity: 0.6; }
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::builder::{callout, paragraph};
+    use crate::fixtures::fetched;
+
+    #[test]
+    fn only_web_mail_and_relative_urls_are_linked() {
+        for url in [
+            "https://example.com/a?b=c:d",
+            "HTTP://example.com",
+            "mailto:team@example.com",
+            "/docs/intro",
+            "intro#part:2",
+        ] {
+            assert!(safe_url(url).is_some(), "{url}");
+        }
+        for url in [
+            "javascript:alert(1)",
+            " JavaScript:alert(1)",
+            "java\tscript:alert(1)",
+            "\u{1}javascript:alert(1)",
+            "data:text/html,<script>alert(1)</script>",
+            "vbscript:msgbox",
+        ] {
+            assert_eq!(safe_url(url), None, "{url}");
+        }
+    }
+
+    #[test]
+    fn unsafe_links_are_plain_text() {
+        let html = HtmlRenderer::new().render_rich_text(&[
+            RichText::text("click").link("javascript:alert(1)"),
+            RichText::text(" or "),
+            RichText::text("visit").link("https://example.com/?a=1&b=\"2\""),
+        ]);
+        assert_eq!(
+            html,
+            "click or <a href=\"https://example.com/?a=1&amp;b=&quot;2&quot;\">visit</a>"
+        );
+    }
+
+    #[test]
+    fn colored_callouts_keep_their_background() {
+        let block = fetched(callout("💡", "Heads up").color(TextColor::BlueBackground));
+        assert_eq!(
+            HtmlRenderer::new().render_block(&block),
+            "<div class=\"notion-callout notion-blue-background\">\n<div class=\"notion-callout-icon\">💡</div>\n<div class=\"notion-callout-text\">Heads up\n</div>\n</div>"
+        );
+        let rule = |selector: &str| STYLESHEET.find(&format!("{selector} {{")).unwrap();
+        assert!(rule(".notion-callout") < rule(".notion-blue-background"));
+        assert!(rule(".notion-callout") < rule(".notion-gray-background"));
+    }
+
+    #[test]
+    fn colors_become_classes() {
+        let block = fetched(paragraph(RichText::text("late").color(TextColor::Red)));
+        let html = HtmlRenderer::new().render_block(&block);
+        assert!(html.contains("class=\"notion-red\""), "{html}");
+        assert_eq!(color_class(&TextColor::Default), None);
+        assert_eq!(
+            color_class(&TextColor::YellowBackground).as_deref(),
+            Some("notion-yellow-background")
+        );
+    }
+}
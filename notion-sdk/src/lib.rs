@@ -11,10 +11,11 @@
 //! ## Examples
 //! ```rust,no_run
 //! use notion_sdk::NotionApi;
-//! async fn main(){
+//! async fn run() -> Result<(), notion_sdk::error::Error> {
 //!     let notion = NotionApi::new("token")?;
 //!     let me = notion.users_me().await;
 //!     println!("{:#?}", me);
+//!     Ok(())
 //! }
 //!
 //! ```
@@ -23,64 +24,284 @@ pub mod comment;
 pub mod common;
 pub mod database;
 pub mod error;
+#[cfg(test)]
+mod fixtures;
+// lets the tests derive `NotionPage`, its expansion names the crate by path
+#[cfg(test)]
+extern crate self as notion_sdk;
+pub mod html;
+pub mod markdown;
 pub mod pages;
 pub mod pagination;
+pub mod plain_text;
+pub mod rate_limit;
+pub mod retry;
 pub mod search;
 pub mod user;
 
 use crate::error::Error;
 use crate::pagination::Object;
-use reqwest::{ClientBuilder, RequestBuilder};
+use crate::rate_limit::RateLimiter;
+use crate::retry::RetryPolicy;
+use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::{ClientBuilder, Method, Request, RequestBuilder};
+use std::time::Duration;
 
 const NOTION_API_VERSION: &str = "2022-02-22";
+const NOTION_API_BASE_PATH: &str = "https://api.notion.com/v1";
 
 /// Notion Api Client
 #[derive(Debug, Clone)]
 pub struct NotionApi {
     base_path: String,
     client: reqwest::Client,
+    headers: HeaderMap,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
 }
 
 /// new a notion api client with api token
 impl NotionApi {
-    pub fn new<T>(api_token: T) -> Result<Self, Error> where
-        T: Into<String> + std::fmt::Display, {
-        let mut headers = reqwest::header::HeaderMap::new();
-        headers.insert(
-            "Notion-Version",
-            reqwest::header::HeaderValue::from_static(NOTION_API_VERSION),
-        );
-        let mut auth_value = reqwest::header::HeaderValue::from_str(&format!("Bearer {api_token}"))
+    pub fn new<T>(api_token: T) -> Result<Self, Error>
+    where
+        T: Into<String> + std::fmt::Display,
+    {
+        NotionApiBuilder::new(api_token).build()
+    }
+    /// configure a client before building it, see [`NotionApiBuilder`]
+    pub fn builder<T>(api_token: T) -> NotionApiBuilder
+    where
+        T: Into<String> + std::fmt::Display,
+    {
+        NotionApiBuilder::new(api_token)
+    }
+    /// replace the retry policy, see [`RetryPolicy`]
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+    /// throttle requests with a [`RateLimiter`], shared with every clone of this client
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+}
+
+/// Notion Api Client Builder
+///
+/// ## Examples
+/// ```rust,no_run
+/// use notion_sdk::NotionApi;
+/// use std::time::Duration;
+/// # fn run() -> Result<(), notion_sdk::error::Error> {
+/// let notion = NotionApi::builder("token")
+///     .base_url("http://127.0.0.1:8080/v1")
+///     .api_version("2022-06-28")
+///     .timeout(Duration::from_secs(30))
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct NotionApiBuilder {
+    api_token: String,
+    base_path: String,
+    api_version: String,
+    user_agent: Option<String>,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    proxy: Option<reqwest::Proxy>,
+    client: Option<reqwest::Client>,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
+}
+
+impl std::fmt::Debug for NotionApiBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NotionApiBuilder")
+            .field("api_token", &"<redacted>")
+            .field("base_path", &self.base_path)
+            .field("api_version", &self.api_version)
+            .field("user_agent", &self.user_agent)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("timeout", &self.timeout)
+            .field("proxy", &self.proxy)
+            .field("client", &self.client)
+            .field("retry_policy", &self.retry_policy)
+            .field("rate_limiter", &self.rate_limiter)
+            .finish()
+    }
+}
+
+impl NotionApiBuilder {
+    /// start from the defaults of [`NotionApi::new`] with an integration token
+    pub fn new<T>(api_token: T) -> Self
+    where
+        T: Into<String> + std::fmt::Display,
+    {
+        NotionApiBuilder {
+            api_token: api_token.into(),
+            base_path: NOTION_API_BASE_PATH.to_owned(),
+            api_version: NOTION_API_VERSION.to_owned(),
+            user_agent: None,
+            connect_timeout: None,
+            timeout: None,
+            proxy: None,
+            client: None,
+            retry_policy: RetryPolicy::default(),
+            rate_limiter: None,
+        }
+    }
+    /// base url of the api, defaults to `https://api.notion.com/v1`
+    pub fn base_url<T: Into<String>>(mut self, base_url: T) -> Self {
+        self.base_path = base_url.into().trim_end_matches('/').to_owned();
+        self
+    }
+    /// value of the `Notion-Version` header, defaults to `2022-02-22`
+    pub fn api_version<T: Into<String>>(mut self, api_version: T) -> Self {
+        self.api_version = api_version.into();
+        self
+    }
+    /// value of the `User-Agent` header
+    pub fn user_agent<T: Into<String>>(mut self, user_agent: T) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+    /// timeout for establishing a connection, ignored when a [`client`](Self::client) is given
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+    /// timeout for a whole request, ignored when a [`client`](Self::client) is given
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+    /// send requests through a proxy, ignored when a [`client`](Self::client) is given
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+    /// use a pre-built http client instead of building one
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+    /// see [`RetryPolicy`]
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+    /// see [`RateLimiter`]
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+    /// build the client, fails when the token or a header value is not a valid header
+    pub fn build(self) -> Result<NotionApi, Error> {
+        let mut headers = HeaderMap::new();
+        let api_version = HeaderValue::from_str(&self.api_version).map_err(|source| {
+            Error::InvalidHeaderValue {
+                header: "Notion-Version",
+                source,
+            }
+        })?;
+        headers.insert("Notion-Version", api_version);
+        let mut auth_value = HeaderValue::from_str(&format!("Bearer {}", self.api_token))
             .map_err(|source| Error::InvalidApiToken { source })?;
         auth_value.set_sensitive(true);
         headers.insert(reqwest::header::AUTHORIZATION, auth_value);
-        let api_client = ClientBuilder::new()
-            .default_headers(headers)
-            .build()
-            .map_err(|source| Error::ErrorBuildingClient { source })?;
+        if let Some(user_agent) = &self.user_agent {
+            let user_agent =
+                HeaderValue::from_str(user_agent).map_err(|source| Error::InvalidHeaderValue {
+                    header: "User-Agent",
+                    source,
+                })?;
+            headers.insert(reqwest::header::USER_AGENT, user_agent);
+        }
+        let client = match self.client {
+            Some(client) => client,
+            None => {
+                let mut builder = ClientBuilder::new();
+                if let Some(timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(proxy) = self.proxy {
+                    builder = builder.proxy(proxy);
+                }
+                builder
+                    .build()
+                    .map_err(|source| Error::ErrorBuildingClient { source })?
+            }
+        };
         Ok(NotionApi {
-            base_path: "https://api.notion.com/v1".to_owned(),
-            client: api_client,
+            base_path: self.base_path,
+            client,
+            headers,
+            retry_policy: self.retry_policy,
+            rate_limiter: self.rate_limiter,
         })
     }
 }
 
 impl NotionApi {
     async fn request(&self, request: RequestBuilder) -> Result<Object, Error> {
-        let request = request.build()?;
-        let json = self
-            .client
-            .execute(request)
-            .await
-            .map_err(|source| Error::RequestFailed { source })?
-            .text()
-            .await
-            .map_err(|source| Error::ResponseIoError { source })?;
-        let result =
-            serde_json::from_str(&json).map_err(|source| Error::JsonParseError { source })?;
-        match result {
-            Object::Error { error } => Err(Error::ApiError { error }),
-            response => Ok(response),
+        let request = request.headers(self.headers.clone()).build()?;
+        let idempotent = matches!(
+            *request.method(),
+            Method::GET | Method::PATCH | Method::DELETE
+        );
+        self.send(request, idempotent).await
+    }
+
+    /// Like [`request`](Self::request) for a `PATCH` which adds content, so it is not sent again after a timeout.
+    async fn request_not_idempotent(&self, request: RequestBuilder) -> Result<Object, Error> {
+        let request = request.headers(self.headers.clone()).build()?;
+        self.send(request, false).await
+    }
+
+    async fn send(&self, mut request: Request, idempotent: bool) -> Result<Object, Error> {
+        let mut attempt = 1;
+        loop {
+            let next = if attempt < self.retry_policy.max_attempts {
+                request.try_clone()
+            } else {
+                None
+            };
+            let (result, retry_after) = self.execute(request).await;
+            let delay = self.retry_policy.delay(attempt, retry_after);
+            match (result, next, delay) {
+                (Err(err), Some(next), Some(delay))
+                    if err.is_retryable() || idempotent && err.is_transient() =>
+                {
+                    tokio::time::sleep(delay).await;
+                    request = next;
+                    attempt += 1;
+                }
+                (result, _, _) => return result,
+            }
+        }
+    }
+
+    async fn execute(&self, request: Request) -> (Result<Object, Error>, Option<Duration>) {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire().await;
         }
+        let response = match self.client.execute(request).await {
+            Ok(response) => response,
+            Err(source) => return (Err(Error::RequestFailed { source }), None),
+        };
+        let retry_after = retry::retry_after(response.headers());
+        let result = match response.text().await {
+            Ok(json) => match serde_json::from_str(&json) {
+                Ok(Object::Error { error }) => Err(Error::ApiError { error }),
+                Ok(response) => Ok(response),
+                Err(source) => Err(Error::JsonParseError { source }),
+            },
+            Err(source) => Err(Error::ResponseIoError { source }),
+        };
+        (result, retry_after)
     }
 }
@@ -0,0 +1,520 @@
+use crate::block::{Block, CodeFields, CodeLanguage, LinkToPageFields, TableFields};
+use crate::common::file::FileOrEmojiObject;
+use crate::common::rich_text::RichText;
+use uuid::Uuid;
+
+/// Render blocks, including the children they were fetched with, as Markdown.
+pub fn to_markdown(blocks: &[Block]) -> String {
+    let mut markdown = blocks_to_markdown(blocks);
+    if !markdown.is_empty() {
+        markdown.push('\n');
+    }
+    markdown
+}
+
+/// Render rich text as inline Markdown, annotations become emphasis, strikethrough and code spans.
+pub fn rich_text_to_markdown(rich_text: &[RichText]) -> String {
+    spans_to_markdown(rich_text, false)
+}
+
+/// `in_table` also escapes pipes in code spans, which would end the cell otherwise
+fn spans_to_markdown(rich_text: &[RichText], in_table: bool) -> String {
+    let mut markdown = String::new();
+    for span in rich_text {
+        let line_start = markdown.is_empty() || markdown.ends_with('\n');
+        markdown.push_str(&span_to_markdown(span, line_start, in_table));
+    }
+    markdown
+}
+
+fn blocks_to_markdown(blocks: &[Block]) -> String {
+    let mut markdown = String::new();
+    let mut number = 0;
+    let mut previous_item = false;
+    for block in blocks {
+        number = match block {
+            Block::NumberedListItem { .. } => number + 1,
+            _ => 0,
+        };
+        let rendered = block_to_markdown(block, number);
+        if rendered.is_empty() {
+            continue;
+        }
+        let item = is_list_item(block);
+        if !markdown.is_empty() {
+            // consecutive items form a tight list
+            markdown.push_str(if previous_item && item { "\n" } else { "\n\n" });
+        }
+        markdown.push_str(&rendered);
+        previous_item = item;
+    }
+    markdown
+}
+
+fn is_list_item(block: &Block) -> bool {
+    matches!(
+        block,
+        Block::BulletedListItem { .. } | Block::NumberedListItem { .. } | Block::ToDo { .. }
+    )
+}
+
+fn block_to_markdown(block: &Block, number: usize) -> String {
+    match block {
+        Block::Paragraph { paragraph, .. } => with_children(
+            rich_text_to_markdown(&paragraph.rich_text),
+            paragraph.children.as_deref(),
+        ),
+        Block::Heading1 { heading_1, .. } => heading(1, &heading_1.rich_text, block.children()),
+        Block::Heading2 { heading_2, .. } => heading(2, &heading_2.rich_text, block.children()),
+        Block::Heading3 { heading_3, .. } => heading(3, &heading_3.rich_text, block.children()),
+        Block::CallOut { call_out, .. } => {
+            let text = rich_text_to_markdown(&call_out.rich_text);
+            let text = match &call_out.icon {
+                FileOrEmojiObject::Emoji { emoji } if !emoji.is_empty() => {
+                    format!("{emoji} {text}")
+                }
+                _ => text,
+            };
+            quote(&with_children(text, call_out.children.as_deref()))
+        }
+        Block::Quote { quote: fields, .. } => quote(&with_children(
+            rich_text_to_markdown(&fields.rich_text),
+            fields.children.as_deref(),
+        )),
+        Block::BulletedListItem {
+            bulleted_list_item, ..
+        } => list_item(
+            "- ",
+            "",
+            &bulleted_list_item.rich_text,
+            bulleted_list_item.children.as_deref(),
+        ),
+        Block::NumberedListItem {
+            numbered_list_item, ..
+        } => list_item(
+            &format!("{number}. "),
+            "",
+            &numbered_list_item.rich_text,
+            numbered_list_item.children.as_deref(),
+        ),
+        Block::ToDo { to_do, .. } => list_item(
+            "- ",
+            if to_do.checked { "[x] " } else { "[ ] " },
+            &to_do.rich_text,
+            to_do.children.as_deref(),
+        ),
+        Block::Toggle { toggle, .. } => {
+            // blank lines end the HTML blocks, so the summary is parsed as Markdown
+            let summary = rich_text_to_markdown(&toggle.rich_text);
+            let mut details = format!("<details>\n<summary>\n\n{summary}\n\n</summary>");
+            if let Some(children) = toggle.children.as_deref().filter(|c| !c.is_empty()) {
+                details.push_str("\n\n");
+                details.push_str(&blocks_to_markdown(children));
+            }
+            details.push_str("\n\n</details>");
+            details
+        }
+        Block::Code { code, .. } => code_block(code),
+        Block::ChildPage { common, child_page } => {
+            format!(
+                "[{}]({})",
+                escape(&child_page.title, false),
+                notion_url(&common.id.0)
+            )
+        }
+        Block::ChildDatabase { common, child_page } => {
+            format!(
+                "[{}]({})",
+                escape(&child_page.title, false),
+                notion_url(&common.id.0)
+            )
+        }
+        Block::Embed { embed, .. } => format!("<{}>", embed.url),
+        Block::Image { image, .. } => format!("![]({})", link_destination(image.url())),
+        Block::Video { video: file, .. }
+        | Block::File { file, .. }
+        | Block::Pdf { pdf: file, .. } => format!("<{}>", file.url()),
+        Block::Bookmark { bookmark, .. } => {
+            if bookmark.caption.is_empty() {
+                format!("<{}>", bookmark.url)
+            } else {
+                format!(
+                    "[{}]({})",
+                    rich_text_to_markdown(&bookmark.caption),
+                    link_destination(&bookmark.url)
+                )
+            }
+        }
+        Block::LinkPreview { link_preview, .. } => format!("<{}>", link_preview.url),
+        Block::Equation { equation, .. } => format!("$$\n{}\n$$", equation.expression.trim()),
+        Block::Divider { .. } => "---".to_string(),
+        Block::ColumnList { column_list, .. } => blocks_to_markdown(&column_list.children),
+        Block::Column { column, .. } => blocks_to_markdown(&column.children),
+        Block::LinkToPage { link_to_page, .. } => match link_to_page {
+            LinkToPageFields::PageId { page_id } => format!("<{}>", notion_url(&page_id.0)),
+            LinkToPageFields::DatabaseId { database_id } => {
+                format!("<{}>", notion_url(&database_id.0))
+            }
+        },
+        Block::Table { table, .. } => table_to_markdown(table),
+        Block::TableRow { table_row, .. } => row_to_markdown(&table_row.cells),
+        Block::SyncedBlock { synced_block, .. } => blocks_to_markdown(&synced_block.children),
+        Block::TableOfContents { .. }
+        | Block::Breadcrumb { .. }
+        | Block::Template { .. }
+        | Block::Unsupported { .. }
+        | Block::Unknown => unsupported(block),
+    }
+}
+
+fn unsupported(block: &Block) -> String {
+    match block.id() {
+        Some(id) => format!("<!-- unsupported block: {} {} -->", block.block_type(), id),
+        None => format!("<!-- unsupported block: {} -->", block.block_type()),
+    }
+}
+
+fn with_children(text: String, children: Option<&[Block]>) -> String {
+    match children.filter(|c| !c.is_empty()) {
+        Some(children) if text.is_empty() => blocks_to_markdown(children),
+        Some(children) => format!("{text}\n\n{}", blocks_to_markdown(children)),
+        None => text,
+    }
+}
+
+fn heading(level: usize, rich_text: &[RichText], children: Option<&[Block]>) -> String {
+    // a heading ends at the line break
+    let text = rich_text_to_markdown(rich_text).replace("\\\n", " ");
+    with_children(format!("{} {}", "#".repeat(level), text), children)
+}
+
+fn quote(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            if line.is_empty() {
+                ">".to_string()
+            } else {
+                format!("> {line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// `marker` is the list marker, its width is the indentation of continuation lines and children
+fn list_item(
+    marker: &str,
+    task: &str,
+    rich_text: &[RichText],
+    children: Option<&[Block]>,
+) -> String {
+    let mut item = format!("{task}{}", rich_text_to_markdown(rich_text));
+    if let Some(children) = children.filter(|c| !c.is_empty()) {
+        // anything but a nested list would otherwise continue the item's paragraph
+        item.push_str(if is_list_item(&children[0]) {
+            "\n"
+        } else {
+            "\n\n"
+        });
+        item.push_str(&blocks_to_markdown(children));
+    }
+    let indent = " ".repeat(marker.len());
+    let mut lines = item.lines();
+    let mut markdown = format!("{marker}{}", lines.next().unwrap_or_default());
+    for line in lines {
+        markdown.push('\n');
+        if !line.is_empty() {
+            markdown.push_str(&indent);
+            markdown.push_str(line);
+        }
+    }
+    markdown
+}
+
+fn code_block(code: &CodeFields) -> String {
+    let source: String = code.rich_text.iter().map(RichText::plain_text).collect();
+    let longest = longest_run(&source, '`');
+    let fence = "`".repeat(longest.max(2) + 1);
+    format!(
+        "{fence}{}\n{}\n{fence}",
+        fence_language(&code.language),
+        source.trim_end_matches('\n')
+    )
+}
+
+/// info string understood by common syntax highlighters
+fn fence_language(language: &CodeLanguage) -> String {
+    match language {
+        CodeLanguage::PlainText => String::new(),
+        CodeLanguage::CPlusPlus => "cpp".to_string(),
+        CodeLanguage::CSharp => "csharp".to_string(),
+        CodeLanguage::FSharp => "fsharp".to_string(),
+        CodeLanguage::ObjectiveC => "objectivec".to_string(),
+        CodeLanguage::VbNet => "vbnet".to_string(),
+        CodeLanguage::VisualBasic => "vb".to_string(),
+        CodeLanguage::JavaCAndCPlusPlusAndCSharp => "java".to_string(),
+        language => serde_json::to_value(language)
+            .ok()
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_default(),
+    }
+}
+
+fn table_to_markdown(table: &TableFields) -> String {
+    let mut rows: Vec<String> = table
+        .children
+        .iter()
+        .filter_map(|row| match row {
+            Block::TableRow { table_row, .. } => Some(row_to_markdown(&table_row.cells)),
+            _ => None,
+        })
+        .collect();
+    // GFM tables always start with a header row
+    let header = if table.has_column_header && !rows.is_empty() {
+        rows.remove(0)
+    } else {
+        format!("|{}", "   |".repeat(table.table_width.max(1) as usize))
+    };
+    let delimiter = format!("|{}", " --- |".repeat(table.table_width.max(1) as usize));
+    let mut markdown = format!("{header}\n{delimiter}");
+    for row in rows {
+        markdown.push('\n');
+        markdown.push_str(&row);
+    }
+    markdown
+}
+
+fn row_to_markdown(cells: &[Vec<RichText>]) -> String {
+    let mut row = String::from("|");
+    for cell in cells {
+        let cell = spans_to_markdown(cell, true).replace("\\\n", "<br>");
+        row.push(' ');
+        row.push_str(&cell);
+        row.push_str(" |");
+    }
+    row
+}
+
+fn span_to_markdown(span: &RichText, line_start: bool, in_table: bool) -> String {
+    let common = span.common();
+    let (text, href) = match span {
+        RichText::Text { text, .. } => (
+            text.content.as_str(),
+            text.link
+                .as_ref()
+                .map(|link| link.url.as_str())
+                .or(common.href.as_deref()),
+        ),
+        RichText::Mention { .. } => (common.plain_text.as_str(), common.href.as_deref()),
+        RichText::Equation { equation, .. } => return format!("${}$", equation.expression.trim()),
+    };
+    let core = text.trim();
+    if core.is_empty() {
+        return text.to_string();
+    }
+    // emphasis markers must touch the text they wrap
+    let leading = &text[..text.len() - text.trim_start().len()];
+    let trailing = &text[text.trim_end().len()..];
+    let annotations = common.annotations.clone().unwrap_or_default();
+    let mut markdown = if annotations.code == Some(true) {
+        code_span(core, in_table)
+    } else {
+        escape(core, line_start || leading.contains('\n'))
+    };
+    if annotations.strikethrough == Some(true) {
+        markdown = format!("~~{markdown}~~");
+    }
+    if annotations.italic == Some(true) {
+        markdown = format!("*{markdown}*");
+    }
+    if annotations.bold == Some(true) {
+        markdown = format!("**{markdown}**");
+    }
+    if annotations.underline == Some(true) {
+        markdown = format!("<u>{markdown}</u>");
+    }
+    if let Some(href) = href {
+        markdown = format!("[{markdown}]({})", link_destination(href));
+    }
+    format!("{leading}{markdown}{trailing}")
+}
+
+fn code_span(code: &str, in_table: bool) -> String {
+    let fence = "`".repeat(longest_run(code, '`') + 1);
+    let code = if in_table {
+        code.replace('|', "\\|")
+    } else {
+        code.to_string()
+    };
+    if code.starts_with('`') || code.ends_with('`') {
+        format!("{fence} {code} {fence}")
+    } else {
+        format!("{fence}{code}{fence}")
+    }
+}
+
+fn longest_run(text: &str, c: char) -> usize {
+    let mut longest = 0;
+    let mut current = 0;
+    for ch in text.chars() {
+        if ch == c {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    longest
+}
+
+/// backslash escape characters with a meaning inside a line, line breaks become hard breaks,
+/// at the start of a line also the markers of headings, lists and setext underlines
+fn escape(text: &str, line_start: bool) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut line_start = line_start;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '~' | '|' | '$' | '&' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '#' | '-' | '+' | '=' if line_start => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '0'..='9' if line_start => {
+                escaped.push(c);
+                while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                    escaped.push(digit);
+                }
+                if let Some(delimiter) = chars.next_if(|c| matches!(c, '.' | ')')) {
+                    escaped.push('\\');
+                    escaped.push(delimiter);
+                }
+            }
+            // the next span may be a link, which would turn into an image
+            '!' if chars.peek().is_none() => escaped.push_str("\\!"),
+            '\n' => {
+                escaped.push_str("\\\n");
+                line_start = true;
+                continue;
+            }
+            ' ' | '\t' if line_start => {
+                escaped.push(c);
+                continue;
+            }
+            c => escaped.push(c),
+        }
+        line_start = false;
+    }
+    escaped
+}
+
+fn link_destination(url: &str) -> String {
+    url.replace(' ', "%20")
+        .replace('(', "%28")
+        .replace(')', "%29")
+}
+
+fn notion_url(id: &Uuid) -> String {
+    format!("https://www.notion.so/{}", id.simple())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::builder::{
+        bulleted_list_item, code, heading_2, paragraph, table, table_row, toggle,
+    };
+    use crate::block::NewBlock;
+    use crate::fixtures::fetched;
+    use crate::markdown::from_markdown;
+    use crate::rich_text;
+
+    fn round_trip(blocks: Vec<NewBlock>) {
+        let fetched: Vec<Block> = blocks.iter().cloned().map(fetched).collect();
+        let markdown = to_markdown(&fetched);
+        assert_eq!(from_markdown(&markdown), blocks, "{markdown}");
+    }
+
+    #[test]
+    fn block_markers_at_the_start_of_a_line_stay_text() {
+        round_trip(vec![
+            paragraph("# not a heading"),
+            paragraph("- not a list"),
+            paragraph("+ not a list either"),
+            paragraph("1. not numbered"),
+            paragraph("2) not numbered"),
+            paragraph("first line\n# second line\n=== third line"),
+            bulleted_list_item("# item"),
+            heading_2("#hashtag"),
+        ]);
+    }
+
+    #[test]
+    fn inline_syntax_stays_text() {
+        round_trip(vec![
+            paragraph("costs $5 and $6, 1 + 1 = 2 - 0"),
+            paragraph("&amp; &lt;b&gt; & more"),
+            paragraph(rich_text![
+                "wow!",
+                RichText::text("a link").link("https://example.com")
+            ]),
+            paragraph(
+                "*stars* _underscores_ `ticks` [brackets] <tags> ~tilde~ | pipe \\ backslash",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn annotations_round_trip() {
+        round_trip(vec![paragraph(rich_text![
+            RichText::text("bold").bold(),
+            " and ",
+            RichText::text("a | b").code(),
+            " and ",
+            RichText::text("gone").strikethrough().italic(),
+        ])]);
+    }
+
+    #[test]
+    fn code_spans_with_pipes_stay_in_their_cell() {
+        round_trip(vec![table(vec![
+            table_row(vec![RichText::text("a | b").code(), RichText::text("c")]),
+            table_row(vec![
+                RichText::text("x || y").code(),
+                RichText::text("1 | 2"),
+            ]),
+        ])
+        .column_header()]);
+    }
+
+    #[test]
+    fn code_blocks_round_trip() {
+        round_trip(vec![code(
+            CodeLanguage::Rust,
+            "fn main() {\n    println!(\"```\");\n}",
+        )]);
+    }
+
+    #[test]
+    fn toggle_summary_keeps_its_formatting() {
+        let markdown = to_markdown(&[fetched(toggle(rich_text![
+            RichText::text("Details").bold(),
+            " for <everyone>",
+        ]))]);
+        assert_eq!(
+            markdown,
+            "<details>\n<summary>\n\n**Details** for \\<everyone\\>\n\n</summary>\n\n</details>\n"
+        );
+        let summary: String = pulldown_cmark::Parser::new(&markdown)
+            .filter_map(|event| match event {
+                pulldown_cmark::Event::Text(text) => Some(text.into_string()),
+                pulldown_cmark::Event::Start(pulldown_cmark::Tag::Strong) => Some("<b>".into()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(summary, "<b>Details for <everyone>");
+    }
+}
@@ -0,0 +1,390 @@
+use crate::block::builder::{
+    bulleted_list_item, divider, equation, heading_1, heading_2, heading_3, image,
+    numbered_list_item, paragraph, quote, table, todo,
+};
+use crate::block::{CodeFields, CodeLanguage, NewBlock, TableRowFields};
+use crate::common::rich_text::{split_long, Annotations, RichText};
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+
+/// Parse a Markdown document into blocks ready to be sent with
+/// [`NotionApi::block_append_children`](crate::NotionApi::block_append_children).
+///
+/// Headings below level 3 become level 3 headings and raw HTML is dropped,
+/// except for `<u>` and `<br>` which are what [`to_markdown`](crate::markdown::to_markdown) writes.
+/// Notion only takes absolute urls, links to relative urls or anchors become plain text
+/// and images with a relative source are replaced by their alternative text.
+pub fn from_markdown(markdown: &str) -> Vec<NewBlock> {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_MATH;
+    let mut importer = Importer {
+        events: Parser::new_ext(markdown, options),
+    };
+    importer.blocks()
+}
+
+struct Importer<'a> {
+    events: Parser<'a>,
+}
+
+impl<'a> Importer<'a> {
+    /// blocks up to the end of the enclosing tag
+    fn blocks(&mut self) -> Vec<NewBlock> {
+        let mut blocks = vec![];
+        while let Some(event) = self.events.next() {
+            match event {
+                Event::Start(tag) => self.block(tag, &mut blocks),
+                Event::End(_) => break,
+                Event::Rule => blocks.push(divider()),
+                _ => {}
+            }
+        }
+        blocks
+    }
+
+    fn block(&mut self, tag: Tag<'a>, blocks: &mut Vec<NewBlock>) {
+        match tag {
+            Tag::Paragraph => {
+                let inline = self.inline();
+                if !inline.is_empty() {
+                    blocks.push(paragraph(inline.spans));
+                }
+                blocks.extend(inline.blocks);
+            }
+            Tag::Heading { level, .. } => {
+                let inline = self.inline();
+                blocks.push(match level {
+                    HeadingLevel::H1 => heading_1(inline.spans),
+                    HeadingLevel::H2 => heading_2(inline.spans),
+                    _ => heading_3(inline.spans),
+                });
+                blocks.extend(inline.blocks);
+            }
+            Tag::BlockQuote(_) => {
+                // the first paragraph is the text of the quote
+                let mut children = self.blocks().into_iter().peekable();
+                let text = match children.next_if(|b| matches!(b, NewBlock::Paragraph { .. })) {
+                    Some(NewBlock::Paragraph { paragraph }) => paragraph.rich_text,
+                    _ => vec![],
+                };
+                blocks.push(with_children(quote(text), children.collect()));
+            }
+            Tag::CodeBlock(kind) => {
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => {
+                        code_language(info.split_whitespace().next().unwrap_or_default())
+                    }
+                    CodeBlockKind::Indented => CodeLanguage::PlainText,
+                };
+                let mut source = String::new();
+                for event in self.events.by_ref() {
+                    match event {
+                        Event::Text(text) => source.push_str(&text),
+                        Event::End(_) => break,
+                        _ => {}
+                    }
+                }
+                if source.ends_with('\n') {
+                    source.pop();
+                }
+                blocks.push(NewBlock::Code {
+                    code: CodeFields {
+                        rich_text: split_long(vec![RichText::text(source)]),
+                        caption: vec![],
+                        language,
+                    },
+                });
+            }
+            Tag::List(start) => self.list(start.is_some(), blocks),
+            Tag::Table(_) => blocks.push(self.table()),
+            Tag::HtmlBlock | Tag::MetadataBlock(_) => {
+                for event in self.events.by_ref() {
+                    if let Event::End(_) = event {
+                        break;
+                    }
+                }
+            }
+            // footnotes and definition lists are kept as plain blocks
+            _ => blocks.extend(self.blocks()),
+        }
+    }
+
+    fn list(&mut self, ordered: bool, blocks: &mut Vec<NewBlock>) {
+        while let Some(event) = self.events.next() {
+            match event {
+                Event::Start(Tag::Item) => blocks.push(self.item(ordered)),
+                Event::End(_) => break,
+                _ => {}
+            }
+        }
+    }
+
+    fn item(&mut self, ordered: bool) -> NewBlock {
+        let mut inline = Inline::default();
+        let mut checked = None;
+        let mut children = vec![];
+        while let Some(event) = self.events.next() {
+            match event {
+                Event::TaskListMarker(done) => checked = Some(done),
+                // the text of an item in a loose list is wrapped in a paragraph
+                Event::Start(Tag::Paragraph) if inline.is_empty() && children.is_empty() => {
+                    inline = self.inline();
+                }
+                Event::End(TagEnd::Item) => break,
+                event => {
+                    if let Err(Event::Start(tag)) = inline.push(event) {
+                        self.block(tag, &mut children);
+                    }
+                }
+            }
+        }
+        let spans = split_long(inline.spans);
+        let item = match checked {
+            Some(done) => todo(done, spans),
+            None if ordered => numbered_list_item(spans),
+            None => bulleted_list_item(spans),
+        };
+        children.splice(0..0, inline.blocks);
+        with_children(item, children)
+    }
+
+    fn table(&mut self) -> NewBlock {
+        let mut rows = vec![];
+        let mut cells = vec![];
+        while let Some(event) = self.events.next() {
+            match event {
+                Event::Start(Tag::TableCell) => cells.push(self.inline().spans),
+                Event::End(TagEnd::TableHead | TagEnd::TableRow) => {
+                    rows.push(NewBlock::TableRow {
+                        table_row: TableRowFields {
+                            cells: std::mem::take(&mut cells),
+                        },
+                    });
+                }
+                Event::End(TagEnd::Table) => break,
+                _ => {}
+            }
+        }
+        // GFM tables always have a header row
+        table(rows).column_header()
+    }
+
+    /// text up to the end of the enclosing block
+    fn inline(&mut self) -> Inline {
+        let mut inline = Inline::default();
+        for event in self.events.by_ref() {
+            if inline.push(event).is_err() {
+                break;
+            }
+        }
+        inline.spans = split_long(inline.spans);
+        inline
+    }
+}
+
+fn with_children(block: NewBlock, children: Vec<NewBlock>) -> NewBlock {
+    if children.is_empty() {
+        block
+    } else {
+        block.children(children)
+    }
+}
+
+/// Spans of one block, and the images and display equations found between them
+#[derive(Default)]
+struct Inline {
+    spans: Vec<RichText>,
+    blocks: Vec<NewBlock>,
+    annotations: Annotations,
+    /// the enclosing links, `None` for the ones Notion can not store
+    links: Vec<Option<String>>,
+    image: Option<String>,
+}
+
+impl Inline {
+    fn is_empty(&self) -> bool {
+        self.spans
+            .iter()
+            .all(|span| span.plain_text().trim().is_empty())
+    }
+
+    /// hand back events which are not part of the text
+    fn push<'a>(&mut self, event: Event<'a>) -> Result<(), Event<'a>> {
+        if self.image.is_some() {
+            // the alternative text has no place in an image block
+            if let Event::End(TagEnd::Image) = event {
+                self.blocks.extend(self.image.take().map(image));
+            }
+            return Ok(());
+        }
+        match event {
+            Event::Text(text) => self.text(&text),
+            Event::Code(text) => {
+                let annotations = self.annotations.clone();
+                self.annotations.code = Some(true);
+                self.text(&text);
+                self.annotations = annotations;
+            }
+            Event::SoftBreak => self.text(" "),
+            Event::HardBreak => self.text("\n"),
+            Event::InlineMath(expression) => self.spans.push(RichText::equation(&*expression)),
+            Event::DisplayMath(expression) => self.blocks.push(equation(expression.trim())),
+            Event::InlineHtml(html) => match html.trim().to_ascii_lowercase().as_str() {
+                "<u>" => self.annotations.underline = Some(true),
+                "</u>" => self.annotations.underline = None,
+                "<br>" | "<br/>" | "<br />" => self.text("\n"),
+                _ => {}
+            },
+            Event::FootnoteReference(label) => self.text(&format!("[^{label}]")),
+            Event::Start(Tag::Emphasis) => self.annotations.italic = Some(true),
+            Event::Start(Tag::Strong) => self.annotations.bold = Some(true),
+            Event::Start(Tag::Strikethrough) => self.annotations.strikethrough = Some(true),
+            Event::Start(Tag::Link { dest_url, .. }) => self.links.push(absolute_url(&dest_url)),
+            Event::Start(Tag::Image { dest_url, .. }) => self.image = absolute_url(&dest_url),
+            // the image had a relative source, its alternative text was kept
+            Event::End(TagEnd::Image) => {}
+            Event::Start(Tag::Superscript | Tag::Subscript) => {}
+            Event::End(TagEnd::Emphasis) => self.annotations.italic = None,
+            Event::End(TagEnd::Strong) => self.annotations.bold = None,
+            Event::End(TagEnd::Strikethrough) => self.annotations.strikethrough = None,
+            Event::End(TagEnd::Link) => {
+                self.links.pop();
+            }
+            Event::End(TagEnd::Superscript | TagEnd::Subscript) => {}
+            event => return Err(event),
+        }
+        Ok(())
+    }
+
+    fn text(&mut self, content: &str) {
+        let annotations =
+            (self.annotations != Annotations::default()).then(|| self.annotations.clone());
+        let href = self.links.last().cloned().flatten();
+        // spans with the same style are merged into one
+        if let Some(RichText::Text { rich_text, text }) = self.spans.last_mut() {
+            if rich_text.annotations == annotations && rich_text.href == href {
+                rich_text.plain_text.push_str(content);
+                text.content.push_str(content);
+                return;
+            }
+        }
+        let mut span = RichText::text(content);
+        if let Some(annotations) = annotations {
+            span = span.annotate(|a| *a = annotations);
+        }
+        if let Some(href) = href {
+            span = span.link(href);
+        }
+        self.spans.push(span);
+    }
+}
+
+/// `url` if it is absolute, which Notion requires for links and external files
+fn absolute_url(url: &str) -> Option<String> {
+    reqwest::Url::parse(url).ok().map(|_| url.to_string())
+}
+
+/// Map the info string of a fenced code block to a Notion language
+fn code_language(info: &str) -> CodeLanguage {
+    let info = info.to_ascii_lowercase();
+    let name = match info.as_str() {
+        "" | "text" | "txt" | "plain" | "plaintext" => "plain text",
+        "rs" => "rust",
+        "js" | "jsx" | "node" => "javascript",
+        "ts" | "tsx" => "typescript",
+        "py" | "python3" => "python",
+        "rb" => "ruby",
+        "sh" | "zsh" | "console" => "shell",
+        "ps1" | "pwsh" => "powershell",
+        "cpp" | "cxx" | "cc" | "hpp" => "c++",
+        "cs" | "csharp" => "c#",
+        "fs" | "fsharp" => "f#",
+        "objc" | "objectivec" => "objective-c",
+        "vbnet" => "vb.net",
+        "vb" => "visual basic",
+        "yml" => "yaml",
+        "md" => "markdown",
+        "dockerfile" => "docker",
+        "golang" => "go",
+        "kt" => "kotlin",
+        "tex" => "latex",
+        "hs" => "haskell",
+        "ex" | "exs" => "elixir",
+        "proto" => "protobuf",
+        "make" => "makefile",
+        "wasm" | "wat" => "webassembly",
+        "svg" => "xml",
+        other => other,
+    };
+    serde_json::from_value(serde_json::Value::String(name.to_string()))
+        .unwrap_or(CodeLanguage::PlainText)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::builder::table_row;
+
+    fn language(markdown: &str) -> CodeLanguage {
+        match from_markdown(markdown).as_slice() {
+            [NewBlock::Code { code }] => code.language.clone(),
+            blocks => panic!("expected one code block, got {blocks:?}"),
+        }
+    }
+
+    #[test]
+    fn nested_lists() {
+        assert_eq!(
+            from_markdown("- a\n  - b\n    1. c\n- [x] d\n"),
+            vec![
+                bulleted_list_item("a").children(vec![
+                    bulleted_list_item("b").children(vec![numbered_list_item("c")])
+                ]),
+                todo(true, "d"),
+            ]
+        );
+    }
+
+    #[test]
+    fn quotes_keep_their_first_paragraph_as_text() {
+        assert_eq!(
+            from_markdown("> first\n>\n> second\n"),
+            vec![quote("first").children(vec![paragraph("second")])]
+        );
+    }
+
+    #[test]
+    fn tables_have_a_header_row() {
+        assert_eq!(
+            from_markdown("| a | b |\n|---|---|\n| 1 | 2 |\n"),
+            vec![table(vec![table_row(vec!["a", "b"]), table_row(vec!["1", "2"])]).column_header()]
+        );
+    }
+
+    #[test]
+    fn fence_languages_map_to_notion_names() {
+        assert_eq!(language("```rs\nfn main() {}\n```"), CodeLanguage::Rust);
+        assert_eq!(language("```py\npass\n```"), CodeLanguage::Python);
+        assert_eq!(language("```Shell\nls\n```"), CodeLanguage::Shell);
+        assert_eq!(language("```\nplain\n```"), CodeLanguage::PlainText);
+        assert_eq!(language("```brainfunk\n+\n```"), CodeLanguage::PlainText);
+        assert_eq!(language("    indented\n"), CodeLanguage::PlainText);
+    }
+
+    #[test]
+    fn only_absolute_urls_are_linked() {
+        let markdown = "[site](https://example.com) [doc](docs/x.md) [top](#top) \
+            ![logo](img/logo.png) ![remote](https://example.com/a.png)";
+        assert_eq!(
+            from_markdown(markdown),
+            vec![
+                paragraph(vec![
+                    RichText::text("site").link("https://example.com"),
+                    RichText::text(" doc top logo "),
+                ]),
+                image("https://example.com/a.png"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,32 @@
+//!
+//! # Notion Markdown
+//!
+//! Convert between blocks and CommonMark with the GitHub Flavored Markdown extensions
+//! for tables, task lists and strikethrough, plus `$` and `$$` math.
+//! Blocks Markdown has no syntax for are kept as HTML comments.
+//!
+//! ## Examples
+//! ```rust,no_run
+//! use notion_sdk::block::BlockTreeOptions;
+//! use notion_sdk::markdown::to_markdown;
+//! use notion_sdk::NotionApi;
+//! # async fn run() -> Result<(), notion_sdk::error::Error> {
+//! let notion = NotionApi::new("token")?;
+//! let page_id: notion_sdk::pages::id::PageId = "3f8d5d7e-6c0a-4d9b-9a51-9f3c2b1e8a10".parse()?;
+//! let blocks = notion.fetch_block_tree(page_id, BlockTreeOptions::default()).await?;
+//! std::fs::write("page.md", to_markdown(&blocks)).ok();
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ```rust
+//! use notion_sdk::markdown::from_markdown;
+//! let blocks = from_markdown("# Install\n\n```sh\ncargo add notion-sdk\n```\n\n- [x] **done**");
+//! assert_eq!(blocks.len(), 3);
+//! ```
+mod export;
+mod import;
+
+pub use crate::common::rich_text::MAX_TEXT_LENGTH;
+pub use export::{rich_text_to_markdown, to_markdown};
+pub use import::from_markdown;
@@ -1,13 +1,67 @@
-use crate::database::properties::PropertyValue;
+use crate::database::date::DateValue;
+use crate::database::properties::{FromPropertyValue, PropertyValue};
+use crate::database::relation::RelationValue;
+use crate::database::select::SelectedValue;
 use crate::pages::Page;
+use crate::user::User;
 
 impl Page {
-    /// get page's title
+    /// get page's title, whatever the name of the database's title property
     pub fn get_title(&self) -> String {
-        if let Some(PropertyValue::Title { title, .. }) = self.properties.properties.get("Title") {
-            let tit: Vec<String> = title.iter().map(|r| r.plain_text().to_string()).collect();
-            return tit.join("");
+        self.properties
+            .properties
+            .values()
+            .find_map(|value| match value {
+                PropertyValue::Title { title, .. } => {
+                    Some(title.iter().map(|r| r.plain_text()).collect())
+                }
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+    /// get the raw value of a property
+    pub fn get_property(&self, name: &str) -> Option<&PropertyValue> {
+        self.properties.properties.get(name)
+    }
+    /// read a property as `T`, `None` if it is missing, empty or of another type
+    ///
+    /// ## Examples
+    /// ```rust,no_run
+    /// # fn run(page: notion_sdk::pages::Page) {
+    /// use serde_json::Number;
+    /// let estimate = page.property::<Number>("Estimate");
+    /// let done = page.property::<bool>("Done").unwrap_or_default();
+    /// # }
+    /// ```
+    pub fn property<T: FromPropertyValue>(&self, name: &str) -> Option<T> {
+        self.get_property(name)
+            .and_then(FromPropertyValue::from_property_value)
+    }
+    /// selected option of a select or status property
+    pub fn get_select(&self, name: &str) -> Option<&SelectedValue> {
+        match self.get_property(name)? {
+            PropertyValue::Select { select: option, .. }
+            | PropertyValue::Status { status: option, .. } => option.as_ref(),
+            _ => None,
+        }
+    }
+    pub fn get_date(&self, name: &str) -> Option<&DateValue> {
+        match self.get_property(name)? {
+            PropertyValue::Date { date, .. } => date.as_ref(),
+            _ => None,
+        }
+    }
+    pub fn get_people(&self, name: &str) -> Option<&[User]> {
+        match self.get_property(name)? {
+            PropertyValue::People { people, .. } => Some(people),
+            _ => None,
+        }
+    }
+    /// pages a relation property links to
+    pub fn get_relation(&self, name: &str) -> Option<&[RelationValue]> {
+        match self.get_property(name)? {
+            PropertyValue::Relation { relation, .. } => relation.as_deref(),
+            _ => None,
         }
-        String::new()
     }
 }
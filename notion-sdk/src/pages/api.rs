@@ -1,17 +1,56 @@
+use crate::block::Block;
 use crate::database::id::PropertyId;
 use crate::pages::id::PageId;
-use crate::pages::{CreatePage, UpdatePage};
+use crate::pages::{CreatePage, Page, PropertyItem, UpdatePage};
+use crate::pagination::FromObject;
 use crate::{Error, NotionApi, Object};
 
 const ROUTER: &str = "pages";
 
 impl NotionApi {
-    /// Retrieve a user
-    pub async fn pages_retrieve(&self, id: PageId) -> Result<Object, Error> {
+    /// Retrieve a page
+    pub async fn pages_retrieve(&self, id: PageId) -> Result<Page, Error> {
+        self.raw_pages_retrieve(id)
+            .await
+            .and_then(FromObject::from_object)
+    }
+    /// Retrieve a page property item,
+    /// a single value, or the first page of values for properties which can hold many
+    pub async fn pages_property(
+        &self,
+        id: PageId,
+        property_id: PropertyId,
+    ) -> Result<PropertyItem, Error> {
+        self.raw_pages_property(id, property_id)
+            .await
+            .and_then(FromObject::from_object)
+    }
+    /// Create a page
+    pub async fn pages_create(&self, page: CreatePage) -> Result<Page, Error> {
+        self.raw_pages_create(page)
+            .await
+            .and_then(FromObject::from_object)
+    }
+    /// Update page properties
+    pub async fn pages_update(&self, id: PageId, page: UpdatePage) -> Result<Page, Error> {
+        self.raw_pages_update(id, page)
+            .await
+            .and_then(FromObject::from_object)
+    }
+    /// Delete a page, Notion answers with the deleted `child_page` block
+    pub async fn pages_delete(&self, id: PageId) -> Result<Block, Error> {
+        self.raw_pages_delete(id)
+            .await
+            .and_then(FromObject::from_object)
+    }
+}
+
+impl NotionApi {
+    pub async fn raw_pages_retrieve(&self, id: PageId) -> Result<Object, Error> {
         let u = format!("{}/{}/{}", self.base_path, ROUTER, id);
         self.request(self.client.get(u)).await
     }
-    pub async fn pages_property(
+    pub async fn raw_pages_property(
         &self,
         id: PageId,
         property_id: PropertyId,
@@ -22,15 +61,15 @@ impl NotionApi {
         );
         self.request(self.client.get(u)).await
     }
-    pub async fn pages_create(&self, page: CreatePage) -> Result<Object, Error> {
+    pub async fn raw_pages_create(&self, page: CreatePage) -> Result<Object, Error> {
         let u = format!("{}/{}/", self.base_path, ROUTER);
         self.request(self.client.post(u).json(&page)).await
     }
-    pub async fn pages_update(&self, id: PageId, page: UpdatePage) -> Result<Object, Error> {
+    pub async fn raw_pages_update(&self, id: PageId, page: UpdatePage) -> Result<Object, Error> {
         let u = format!("{}/{}/{}", self.base_path, ROUTER, id);
         self.request(self.client.patch(u).json(&page)).await
     }
-    pub async fn pages_delete(&self, id: PageId) -> Result<Object, Error> {
+    pub async fn raw_pages_delete(&self, id: PageId) -> Result<Object, Error> {
         let u = format!("{}/blocks/{}", self.base_path, id);
         self.request(self.client.delete(u)).await
     }
@@ -18,11 +18,13 @@ mod api;
 mod builder;
 pub mod id;
 
-use crate::block::Block;
+use crate::block::NewBlock;
 use crate::common::file::FileOrEmojiObject;
 use crate::common::parent::Parent;
-use crate::database::properties::Properties;
+use crate::database::properties::{Properties, PropertyValue};
+use crate::error::Error;
 use crate::pages::id::PageId;
+use crate::pagination::ListResponse;
 use crate::user::UserCommon;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -45,12 +47,26 @@ pub struct Page {
     pub parent: Parent,
 }
 
+/// A type stored in the properties of database pages.
+///
+/// Derive it with `#[derive(NotionPage)]` from the `derive` feature
+/// instead of converting every property by hand.
+pub trait NotionPage: Sized {
+    /// Read the properties of a page.
+    fn from_page(page: &Page) -> Result<Self, Error>;
+    /// Properties to create or update a page with, read only properties are left out.
+    fn to_properties(&self) -> Properties;
+}
+
+#[cfg(feature = "derive")]
+pub use notion_sdk_derive::NotionPage;
+
 #[derive(Serialize, Debug, Eq, PartialEq)]
 pub struct CreatePage {
     pub icon: Option<FileOrEmojiObject>,
     pub parent: Parent,
     pub properties: Properties,
-    pub children: Vec<Block>,
+    pub children: Vec<NewBlock>,
 }
 
 #[derive(Serialize, Debug, Eq, PartialEq)]
@@ -59,3 +75,110 @@ pub struct UpdatePage {
     pub properties: Properties,
     pub archived: bool,
 }
+
+/// A page property as [`pages_property`](crate::NotionApi::pages_property) returns it
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum PropertyItem {
+    /// a property with a single value, like `number`, `select` or `date`
+    Value(PropertyValue),
+    /// a page of the values of a `title`, `rich_text`, `relation`, `people` or `rollup` property
+    List(ListResponse<PropertyValue>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use notion_sdk_derive::NotionPage;
+    use serde_json::{json, Value};
+
+    #[derive(NotionPage, Debug)]
+    struct Task {
+        #[notion(name = "Name", kind = "title")]
+        name: String,
+        #[notion(name = "Done", kind = "checkbox")]
+        done: bool,
+        #[notion(name = "Points", kind = "number")]
+        points: f64,
+        #[notion(name = "Estimate", kind = "number")]
+        estimate: Option<f64>,
+    }
+
+    /// a page of [`Task`] with `properties` next to its title
+    fn task_page(properties: Value) -> Page {
+        let mut properties = properties;
+        properties["Name"] = json!({"type": "title", "title": [{
+            "type": "text",
+            "text": {"content": "Write docs"},
+            "plain_text": "Write docs",
+            "annotations": {"bold": false, "italic": false, "strikethrough": false,
+                            "underline": false, "code": false, "color": "default"},
+        }]});
+        fixtures::page(1, properties)
+    }
+
+    fn invalid(result: Result<Task, Error>) -> (String, &'static str) {
+        match result {
+            Err(Error::InvalidPageProperty { name, kind }) => (name, kind),
+            result => panic!("expected an invalid property, got {result:?}"),
+        }
+    }
+
+    #[test]
+    fn properties_are_read_into_fields() {
+        let page = task_page(json!({
+            "Done": {"type": "checkbox", "checkbox": true},
+            "Points": {"type": "number", "number": 3},
+            "Estimate": {"type": "number", "number": 2.5},
+        }));
+        let task = Task::try_from(&page).unwrap();
+        assert_eq!(task.name, "Write docs");
+        assert!(task.done);
+        assert_eq!(task.points, 3.0);
+        assert_eq!(task.estimate, Some(2.5));
+    }
+
+    #[test]
+    fn missing_properties_fail_unless_optional() {
+        let page = task_page(json!({"Points": {"type": "number", "number": 3}}));
+        assert_eq!(
+            invalid(Task::from_page(&page)),
+            ("Done".to_string(), "checkbox")
+        );
+        let page = task_page(json!({
+            "Done": {"type": "checkbox", "checkbox": false},
+            "Points": {"type": "number", "number": 3},
+        }));
+        assert_eq!(Task::from_page(&page).unwrap().estimate, None);
+    }
+
+    #[test]
+    fn empty_properties_fail_unless_optional() {
+        let page = task_page(json!({
+            "Done": {"type": "checkbox", "checkbox": false},
+            "Points": {"type": "number", "number": null},
+        }));
+        assert_eq!(
+            invalid(Task::from_page(&page)),
+            ("Points".to_string(), "number")
+        );
+        let page = task_page(json!({
+            "Done": {"type": "checkbox", "checkbox": false},
+            "Points": {"type": "number", "number": 3},
+            "Estimate": {"type": "number", "number": null},
+        }));
+        assert_eq!(Task::from_page(&page).unwrap().estimate, None);
+    }
+
+    #[test]
+    fn properties_of_another_type_fail() {
+        let page = task_page(json!({
+            "Done": {"type": "rich_text", "rich_text": []},
+            "Points": {"type": "number", "number": 3},
+        }));
+        assert_eq!(
+            invalid(Task::try_from(&page)),
+            ("Done".to_string(), "checkbox")
+        );
+    }
+}
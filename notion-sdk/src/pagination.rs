@@ -2,13 +2,16 @@
 //! # Notion Pagination
 //!
 use crate::block::Block;
+use crate::comment::Comment;
 use crate::database::properties::PropertyValue;
 use crate::database::Database;
 use crate::error::ErrorResponse;
-use crate::pages::Page;
+use crate::pages::{Page, PropertyItem};
 use crate::user::User;
 use crate::Error;
+use futures::{stream, Stream, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
+use std::future::Future;
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone)]
 #[serde(transparent)]
@@ -26,6 +29,46 @@ pub trait Pageable {
     fn start_from(self, starting_point: Option<PagingCursor>) -> Self;
 }
 
+/// Lazily follow `next_cursor` while `has_more` is set,
+/// `fetch` receives the cursor of the page to load, `None` for the first one.
+pub(crate) fn paginate<'a, T, F, Fut>(fetch: F) -> impl Stream<Item = Result<T, Error>> + 'a
+where
+    T: 'a,
+    F: FnMut(Option<PagingCursor>) -> Fut + 'a,
+    Fut: Future<Output = Result<ListResponse<T>, Error>> + 'a,
+{
+    stream::try_unfold((fetch, Some(None)), |(mut fetch, cursor)| async move {
+        let cursor = match cursor {
+            Some(cursor) => cursor,
+            None => return Ok::<_, Error>(None),
+        };
+        let page = fetch(cursor).await?;
+        let next = page.next_cursor.filter(|_| page.has_more).map(Some);
+        let items = stream::iter(page.results.into_iter().map(Ok));
+        Ok(Some((items, (fetch, next))))
+    })
+    .try_flatten()
+}
+
+/// Drain a paginated stream into a vector, stop fetching after `limit` items when given.
+///
+/// ## Examples
+/// ```rust,no_run
+/// use notion_sdk::pagination::collect_all;
+/// use notion_sdk::NotionApi;
+/// # async fn run() -> Result<(), notion_sdk::error::Error> {
+/// let notion = NotionApi::new("token")?;
+/// let users = collect_all(notion.users_list_stream(), Some(500)).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn collect_all<T, S>(stream: S, limit: Option<usize>) -> Result<Vec<T>, Error>
+where
+    S: Stream<Item = Result<T, Error>>,
+{
+    stream.take(limit.unwrap_or(usize::MAX)).try_collect().await
+}
+
 /// <https://developers.notion.com/reference/pagination#responses>
 #[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Clone)]
 pub struct ListResponse<T> {
@@ -57,55 +100,81 @@ impl ListResponse<Object> {
         }
     }
 
-    pub fn expect_databases(self) -> Result<ListResponse<Database>, Error> {
-        let databases: Result<Vec<_>, _> = self
-            .results
-            .into_iter()
-            .map(|object| match object {
-                Object::Database { database } => Ok(database),
-                response => Err(Error::UnexpectedResponse { response }),
-            })
-            .collect();
+    /// convert every result, failing on the first one of another type
+    pub fn expect<T: FromObject>(self) -> Result<ListResponse<T>, Error> {
+        let items: Result<Vec<_>, _> = self.results.into_iter().map(T::from_object).collect();
 
         Ok(ListResponse {
-            results: databases?,
+            results: items?,
             has_more: self.has_more,
             next_cursor: self.next_cursor,
         })
     }
 
+    pub fn expect_databases(self) -> Result<ListResponse<Database>, Error> {
+        self.expect()
+    }
+
     pub fn expect_pages(self) -> Result<ListResponse<Page>, Error> {
-        let items: Result<Vec<_>, _> = self
-            .results
-            .into_iter()
-            .map(|object| match object {
-                Object::Page { page } => Ok(page),
-                response => Err(Error::UnexpectedResponse { response }),
-            })
-            .collect();
+        self.expect()
+    }
 
-        Ok(ListResponse {
-            results: items?,
-            has_more: self.has_more,
-            next_cursor: self.next_cursor,
-        })
+    pub fn expect_blocks(self) -> Result<ListResponse<Block>, Error> {
+        self.expect()
     }
+}
 
-    pub fn expect_blocks(self) -> Result<ListResponse<Block>, crate::Error> {
-        let items: Result<Vec<_>, _> = self
-            .results
-            .into_iter()
-            .map(|object| match object {
-                Object::Block { block } => Ok(block),
-                response => Err(Error::UnexpectedResponse { response }),
-            })
-            .collect();
+/// Conversion from the untyped [`Object`] an endpoint responds with,
+/// any other kind of object is an [`Error::UnexpectedResponse`].
+pub trait FromObject: Sized {
+    fn from_object(object: Object) -> Result<Self, Error>;
+}
 
-        Ok(ListResponse {
-            results: items?,
-            has_more: self.has_more,
-            next_cursor: self.next_cursor,
-        })
+impl FromObject for Object {
+    fn from_object(object: Object) -> Result<Self, Error> {
+        Ok(object)
+    }
+}
+
+impl<T: FromObject> FromObject for ListResponse<T> {
+    fn from_object(object: Object) -> Result<Self, Error> {
+        match object {
+            Object::List { list } => list.expect(),
+            response => Err(Error::UnexpectedResponse {
+                response: Box::new(response),
+            }),
+        }
+    }
+}
+
+macro_rules! from_object {
+    ($ty:ty, $variant:ident, $field:ident) => {
+        impl FromObject for $ty {
+            fn from_object(object: Object) -> Result<Self, Error> {
+                match object {
+                    Object::$variant { $field } => Ok($field),
+                    response => Err(Error::UnexpectedResponse {
+                        response: Box::new(response),
+                    }),
+                }
+            }
+        }
+    };
+}
+
+from_object!(Block, Block, block);
+from_object!(Database, Database, database);
+from_object!(Page, Page, page);
+from_object!(User, User, user);
+from_object!(Comment, Comment, comment);
+from_object!(PropertyValue, PropertyItem, property_item);
+
+impl FromObject for PropertyItem {
+    fn from_object(object: Object) -> Result<Self, Error> {
+        match object {
+            Object::List { list } => list.expect().map(PropertyItem::List),
+            object => PropertyValue::from_object(object).map(PropertyItem::Value),
+        }
     }
 }
 
@@ -133,6 +202,10 @@ pub enum Object {
         #[serde(flatten)]
         user: User,
     },
+    Comment {
+        #[serde(flatten)]
+        comment: Comment,
+    },
     PropertyItem {
         #[serde(flatten)]
         property_item: PropertyValue,
@@ -148,3 +221,72 @@ impl Object {
         matches!(self, Object::Database { .. })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// pages of `items` by `size`, the cursor of a page is the index of its first item
+    fn pages(
+        items: u32,
+        size: u32,
+        fetched: &RefCell<Vec<Option<PagingCursor>>>,
+    ) -> impl Stream<Item = Result<u32, Error>> + '_ {
+        paginate(move |cursor: Option<PagingCursor>| {
+            fetched.borrow_mut().push(cursor.clone());
+            let start: u32 = cursor.map_or(0, |cursor| cursor.0.parse().unwrap());
+            let end = items.min(start + size);
+            async move {
+                Ok(ListResponse {
+                    results: (start..end).collect(),
+                    next_cursor: Some(PagingCursor(end.to_string())),
+                    has_more: end < items,
+                })
+            }
+        })
+    }
+
+    fn cursor(index: &str) -> Option<PagingCursor> {
+        Some(PagingCursor(index.to_string()))
+    }
+
+    #[tokio::test]
+    async fn cursors_are_followed_while_there_are_more() {
+        let fetched = RefCell::new(vec![]);
+        let items = collect_all(pages(7, 3, &fetched), None).await.unwrap();
+        assert_eq!(items, (0..7).collect::<Vec<_>>());
+        assert_eq!(*fetched.borrow(), vec![None, cursor("3"), cursor("6")]);
+    }
+
+    #[tokio::test]
+    async fn limits_stop_fetching() {
+        let fetched = RefCell::new(vec![]);
+        let items = collect_all(pages(10, 3, &fetched), Some(4)).await.unwrap();
+        assert_eq!(items, vec![0, 1, 2, 3]);
+        assert_eq!(*fetched.borrow(), vec![None, cursor("3")]);
+    }
+
+    #[tokio::test]
+    async fn errors_end_the_stream() {
+        let mut calls = 0;
+        let stream = paginate(|start: Option<PagingCursor>| {
+            calls += 1;
+            async move {
+                match start {
+                    None => Ok(ListResponse {
+                        results: vec![1],
+                        next_cursor: cursor("1"),
+                        has_more: true,
+                    }),
+                    Some(_) => Err(Error::JsonParseError {
+                        source: serde_json::from_str::<u32>("").unwrap_err(),
+                    }),
+                }
+            }
+        });
+        let error = collect_all(stream, None).await.unwrap_err();
+        assert!(matches!(error, Error::JsonParseError { .. }));
+        assert_eq!(calls, 2);
+    }
+}
@@ -0,0 +1,163 @@
+//!
+//! # Notion Plain Text
+//!
+//! Flatten blocks to text without any markup, for search indexes and text analytics.
+//! Only what a reader sees as text is kept: urls of media, embeds and bookmarks without a caption are dropped.
+//!
+//! ## Examples
+//! ```rust,no_run
+//! use notion_sdk::block::BlockTreeOptions;
+//! use notion_sdk::plain_text::{to_plain_text, PlainTextOptions};
+//! use notion_sdk::NotionApi;
+//! # async fn run() -> Result<(), notion_sdk::error::Error> {
+//! let notion = NotionApi::new("token")?;
+//! let page_id: notion_sdk::pages::id::PageId = "3f8d5d7e-6c0a-4d9b-9a51-9f3c2b1e8a10".parse()?;
+//! let page = notion.pages_retrieve(page_id.clone()).await?;
+//! let blocks = notion.fetch_block_tree(page_id, BlockTreeOptions::default()).await?;
+//! let options = PlainTextOptions {
+//!     block_separator: "\n\n".to_string(),
+//!     ..Default::default()
+//! };
+//! let document = format!("{}\n\n{}", page.get_title(), to_plain_text(&blocks, &options));
+//! # Ok(())
+//! # }
+//! ```
+use crate::block::Block;
+use crate::common::rich_text::RichText;
+
+/// How blocks are joined into one text.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PlainTextOptions {
+    /// Put between two blocks, a newline by default.
+    pub block_separator: String,
+    /// Put between the cells of a table row, a tab by default.
+    pub cell_separator: String,
+    /// Put in front of every line once per level of nesting, two spaces by default.
+    pub indent: String,
+    /// Keep `- `, `1. ` and `[x] ` in front of list items and to-dos.
+    pub list_markers: bool,
+}
+
+impl Default for PlainTextOptions {
+    fn default() -> Self {
+        PlainTextOptions {
+            block_separator: "\n".to_string(),
+            cell_separator: "\t".to_string(),
+            indent: "  ".to_string(),
+            list_markers: false,
+        }
+    }
+}
+
+/// Join the plain text of the spans.
+pub fn rich_text_to_plain_text(rich_text: &[RichText]) -> String {
+    rich_text.iter().map(RichText::plain_text).collect()
+}
+
+/// Flatten blocks, including the children they were fetched with.
+pub fn to_plain_text(blocks: &[Block], options: &PlainTextOptions) -> String {
+    let mut parts = vec![];
+    collect(blocks, 0, options, &mut parts);
+    parts.join(&options.block_separator)
+}
+
+fn collect(blocks: &[Block], depth: usize, options: &PlainTextOptions, parts: &mut Vec<String>) {
+    let mut number = 0;
+    for block in blocks {
+        number = match block {
+            Block::NumberedListItem { .. } => number + 1,
+            _ => 0,
+        };
+        let text = match block {
+            Block::Paragraph {
+                paragraph: text, ..
+            }
+            | Block::Quote { quote: text, .. }
+            | Block::Toggle { toggle: text, .. } => rich_text_to_plain_text(&text.rich_text),
+            Block::Heading1 {
+                heading_1: heading, ..
+            }
+            | Block::Heading2 {
+                heading_2: heading, ..
+            }
+            | Block::Heading3 {
+                heading_3: heading, ..
+            } => rich_text_to_plain_text(&heading.rich_text),
+            Block::BulletedListItem {
+                bulleted_list_item, ..
+            } => marker(
+                "- ",
+                rich_text_to_plain_text(&bulleted_list_item.rich_text),
+                options,
+            ),
+            Block::NumberedListItem {
+                numbered_list_item, ..
+            } => marker(
+                &format!("{number}. "),
+                rich_text_to_plain_text(&numbered_list_item.rich_text),
+                options,
+            ),
+            Block::ToDo { to_do, .. } => marker(
+                if to_do.checked { "[x] " } else { "[ ] " },
+                rich_text_to_plain_text(&to_do.rich_text),
+                options,
+            ),
+            Block::CallOut { call_out, .. } => rich_text_to_plain_text(&call_out.rich_text),
+            Block::Code { code, .. } => rich_text_to_plain_text(&code.rich_text),
+            Block::Equation { equation, .. } => equation.expression.clone(),
+            Block::ChildPage { child_page, .. } => child_page.title.clone(),
+            Block::ChildDatabase { child_page, .. } => child_page.title.clone(),
+            Block::Bookmark { bookmark, .. } => rich_text_to_plain_text(&bookmark.caption),
+            Block::Template { template, .. } => rich_text_to_plain_text(&template.rich_text),
+            Block::Table { table, .. } => table
+                .children
+                .iter()
+                .filter_map(|row| match row {
+                    Block::TableRow { table_row, .. } => Some(
+                        table_row
+                            .cells
+                            .iter()
+                            .map(|cell| rich_text_to_plain_text(cell))
+                            .collect::<Vec<_>>()
+                            .join(&options.cell_separator),
+                    ),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            _ => String::new(),
+        };
+        if !text.trim().is_empty() {
+            parts.push(indent(&text, depth, options));
+        }
+        // layout blocks only hold other blocks, their children are not nested
+        let depth = match block {
+            Block::ColumnList { .. } | Block::Column { .. } | Block::SyncedBlock { .. } => depth,
+            _ => depth + 1,
+        };
+        if let Some(children) = block.children() {
+            if !matches!(block, Block::Table { .. }) {
+                collect(children, depth, options, parts);
+            }
+        }
+    }
+}
+
+fn marker(marker: &str, text: String, options: &PlainTextOptions) -> String {
+    if options.list_markers {
+        format!("{marker}{text}")
+    } else {
+        text
+    }
+}
+
+fn indent(text: &str, depth: usize, options: &PlainTextOptions) -> String {
+    if depth == 0 || options.indent.is_empty() {
+        return text.to_string();
+    }
+    let indent = options.indent.repeat(depth);
+    text.lines()
+        .map(|line| format!("{indent}{line}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
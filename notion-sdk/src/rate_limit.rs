@@ -0,0 +1,154 @@
+//!
+//! # Notion Rate Limit
+//!
+//! Notion allows an integration an average of three requests per second.
+//! A [`RateLimiter`] is a token bucket shared by every clone of a [`NotionApi`](crate::NotionApi),
+//! requests wait in line for a token instead of being answered with `rate_limited`.
+//!
+//! ## Examples
+//! ```rust,no_run
+//! use notion_sdk::rate_limit::RateLimiter;
+//! use notion_sdk::NotionApi;
+//! # fn run() -> Result<(), notion_sdk::error::Error> {
+//! let notion = NotionApi::new("token")?.with_rate_limiter(RateLimiter::new(3.0, 3));
+//! // every clone draws from the same bucket
+//! let worker = notion.clone();
+//! # Ok(())
+//! # }
+//! ```
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+/// Requests per second Notion allows on average.
+pub const NOTION_REQUESTS_PER_SECOND: f64 = 3.0;
+
+/// Token bucket limiting how fast requests are sent.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    requests_per_second: f64,
+    burst: f64,
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.requests_per_second).min(self.burst);
+        self.updated = now;
+    }
+}
+
+impl Default for RateLimiter {
+    /// Notion's documented budget of three requests per second, with a burst of three.
+    fn default() -> Self {
+        RateLimiter::new(NOTION_REQUESTS_PER_SECOND, 3)
+    }
+}
+
+impl RateLimiter {
+    /// Allow `requests_per_second` on average and up to `burst` requests at once.
+    ///
+    /// The bucket starts full. A `burst` of `0` is treated as `1`.
+    ///
+    /// # Panics
+    /// If `requests_per_second` is not a positive number.
+    pub fn new(requests_per_second: f64, burst: u32) -> Self {
+        assert!(
+            requests_per_second.is_finite() && requests_per_second > 0.0,
+            "requests_per_second must be a positive number"
+        );
+        let burst = f64::from(burst.max(1));
+        RateLimiter {
+            bucket: Arc::new(Mutex::new(Bucket {
+                requests_per_second,
+                burst,
+                tokens: burst,
+                updated: Instant::now(),
+            })),
+        }
+    }
+
+    /// Wait until a request may be sent and take its token.
+    ///
+    /// Callers are served in the order they started waiting.
+    pub async fn acquire(&self) {
+        let mut bucket = self.bucket.lock().await;
+        bucket.refill(Instant::now());
+        if bucket.tokens < 1.0 {
+            let wait = (1.0 - bucket.tokens) / bucket.requests_per_second;
+            tokio::time::sleep(Duration::from_secs_f64(wait)).await;
+            bucket.refill(Instant::now());
+        }
+        bucket.tokens = (bucket.tokens - 1.0).max(0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::future::Future;
+
+    /// Run on a runtime whose clock only moves when every task is waiting on a timer.
+    fn paused<F: Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .start_paused(true)
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    async fn acquire_many(limiter: &RateLimiter, count: usize) -> Duration {
+        let start = Instant::now();
+        for _ in 0..count {
+            limiter.acquire().await;
+        }
+        start.elapsed()
+    }
+
+    #[test]
+    fn fourth_request_in_a_second_waits_for_a_token() {
+        paused(async {
+            let limiter = RateLimiter::new(3.0, 3);
+            assert_eq!(acquire_many(&limiter, 3).await, Duration::ZERO);
+            let waited = acquire_many(&limiter, 1).await;
+            assert!(
+                waited >= Duration::from_millis(333) && waited <= Duration::from_millis(335),
+                "waited {waited:?}"
+            );
+        });
+    }
+
+    #[test]
+    fn burst_refills_while_idle() {
+        paused(async {
+            let limiter = RateLimiter::new(3.0, 3);
+            acquire_many(&limiter, 3).await;
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            assert_eq!(acquire_many(&limiter, 3).await, Duration::ZERO);
+            // idling longer does not save up more than the burst
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            assert_eq!(acquire_many(&limiter, 3).await, Duration::ZERO);
+            assert!(acquire_many(&limiter, 1).await >= Duration::from_millis(333));
+        });
+    }
+
+    #[test]
+    fn clones_share_the_bucket() {
+        paused(async {
+            let limiter = RateLimiter::new(3.0, 3);
+            let clone = limiter.clone();
+            acquire_many(&limiter, 2).await;
+            assert_eq!(acquire_many(&clone, 1).await, Duration::ZERO);
+            assert!(acquire_many(&clone, 1).await >= Duration::from_millis(333));
+        });
+    }
+}
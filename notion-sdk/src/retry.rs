@@ -0,0 +1,280 @@
+//!
+//! # Notion Retry
+//!
+//! Notion answers with `rate_limited` or `database_connection_unavailable` when a request
+//! should simply be sent again later.
+//! [`RetryPolicy`] decides how often and how long [`NotionApi`](crate::NotionApi) waits before doing so.
+//! Requests which timed out, or failed with `service_unavailable` or `conflict_error`,
+//! are only sent again when they are `GET`, `PATCH` or `DELETE` and do not append block children,
+//! since Notion may have created a page, block or comment before the response was lost.
+//!
+//! ## Examples
+//! ```rust,no_run
+//! use notion_sdk::retry::RetryPolicy;
+//! use notion_sdk::NotionApi;
+//! use std::time::Duration;
+//! # fn run() -> Result<(), notion_sdk::error::Error> {
+//! let notion = NotionApi::new("token")?.with_retry_policy(RetryPolicy {
+//!     max_attempts: 5,
+//!     initial_backoff: Duration::from_secs(1),
+//!     ..Default::default()
+//! });
+//! # Ok(())
+//! # }
+//! ```
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use std::time::Duration;
+
+/// How failed requests are retried.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one. `1` disables retrying.
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled on every following attempt.
+    pub initial_backoff: Duration,
+    /// Upper bound for a computed backoff delay.
+    pub max_backoff: Duration,
+    /// Pick a random delay between half and the full computed backoff,
+    /// so that concurrent clients do not retry in lockstep.
+    pub jitter: bool,
+    /// Wait as long as the `Retry-After` response header asks for instead of the computed backoff,
+    /// and give up when that is longer than `max_backoff`.
+    pub respect_retry_after: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            jitter: true,
+            respect_retry_after: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Send every request exactly once.
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Exponential backoff before retry number `attempt` (starting at 1).
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31);
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(1 << exponent)
+            .min(self.max_backoff);
+        if self.jitter {
+            let half = backoff / 2;
+            half + backoff.saturating_sub(half).mul_f64(fastrand::f64())
+        } else {
+            backoff
+        }
+    }
+
+    /// Delay before retry number `attempt`, taking the server supplied `Retry-After` into account,
+    /// `None` when the server asks to wait longer than `max_backoff`.
+    pub fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        match retry_after {
+            Some(retry_after) if self.respect_retry_after => {
+                (retry_after <= self.max_backoff).then_some(retry_after)
+            }
+            _ => Some(self.backoff(attempt)),
+        }
+    }
+}
+
+/// Parse the `Retry-After` header, which Notion sends as a number of seconds.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+        .map(Duration::from_secs_f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{Error, ErrorCode};
+    use crate::search::SearchRequest;
+    use crate::NotionApi;
+    use reqwest::header::HeaderValue;
+    use serde_json::json;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn policy(jitter: bool) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(1000),
+            jitter,
+            respect_retry_after: true,
+        }
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let policy = policy(false);
+        let delays: Vec<_> = (1..=6).map(|attempt| policy.backoff(attempt)).collect();
+        let millis = [100, 200, 400, 800, 1000, 1000].map(Duration::from_millis);
+        assert_eq!(delays, millis);
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn jitter_stays_between_half_and_full_backoff() {
+        let jittered = policy(true);
+        for attempt in 1..=6 {
+            let full = policy(false).backoff(attempt);
+            for _ in 0..100 {
+                let delay = jittered.backoff(attempt);
+                assert!(delay >= full / 2 && delay <= full, "{delay:?} for {full:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn retry_after_is_waited_in_full_or_not_at_all() {
+        let policy = policy(false);
+        assert_eq!(
+            policy.delay(1, Some(Duration::from_millis(700))),
+            Some(Duration::from_millis(700))
+        );
+        assert_eq!(policy.delay(1, Some(Duration::from_secs(5))), None);
+        assert_eq!(policy.delay(2, None), Some(Duration::from_millis(200)));
+        let ignored = RetryPolicy {
+            respect_retry_after: false,
+            ..policy
+        };
+        assert_eq!(
+            ignored.delay(1, Some(Duration::from_secs(5))),
+            Some(Duration::from_millis(100))
+        );
+    }
+
+    #[test]
+    fn retry_after_header_is_parsed_as_seconds() {
+        let parse = |value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(RETRY_AFTER, HeaderValue::from_str(value).unwrap());
+            retry_after(&headers)
+        };
+        assert_eq!(parse("2"), Some(Duration::from_secs(2)));
+        assert_eq!(parse(" 0.5 "), Some(Duration::from_millis(500)));
+        assert_eq!(parse("-1"), None);
+        assert_eq!(parse("NaN"), None);
+        assert_eq!(parse("Wed, 21 Oct 2026 07:28:00 GMT"), None);
+        assert_eq!(retry_after(&HeaderMap::new()), None);
+    }
+
+    fn api_error(status: u16, code: &str) -> ResponseTemplate {
+        ResponseTemplate::new(status).set_body_json(json!({
+            "object": "error",
+            "status": status,
+            "code": code,
+            "message": code,
+        }))
+    }
+
+    async fn client(server: &MockServer) -> NotionApi {
+        NotionApi::builder("token")
+            .base_url(format!("{}/v1", server.uri()))
+            .retry_policy(RetryPolicy {
+                initial_backoff: Duration::from_millis(1),
+                ..policy(false)
+            })
+            .build()
+            .unwrap()
+    }
+
+    fn code(result: Result<impl std::fmt::Debug, Error>) -> ErrorCode {
+        match result {
+            Err(Error::ApiError { error }) => error.code,
+            other => panic!("expected an api error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn rate_limited_requests_are_retried_until_they_succeed() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/search"))
+            .respond_with(api_error(429, "rate_limited"))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/search"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({"object": "list", "results": [], "has_more": false})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        let response = client(&server).await.search(SearchRequest::default()).await;
+        assert!(response.is_ok(), "{response:?}");
+    }
+
+    #[tokio::test]
+    async fn unavailable_is_retried_for_idempotent_requests_only() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/users/me"))
+            .respond_with(api_error(503, "service_unavailable"))
+            .expect(3)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/search"))
+            .respond_with(api_error(409, "conflict_error"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let notion = client(&server).await;
+        assert_eq!(code(notion.users_me().await), ErrorCode::ServiceUnavailable);
+        assert_eq!(
+            code(notion.search(SearchRequest::default()).await),
+            ErrorCode::ConflictError
+        );
+    }
+
+    #[tokio::test]
+    async fn retry_after_longer_than_max_backoff_is_not_waited_for() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/users/me"))
+            .respond_with(api_error(429, "rate_limited").insert_header("Retry-After", "60"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let notion = client(&server).await;
+        assert_eq!(code(notion.users_me().await), ErrorCode::RateLimited);
+    }
+
+    #[tokio::test]
+    async fn other_errors_are_not_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/users/me"))
+            .respond_with(api_error(400, "validation_error"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let notion = client(&server).await;
+        assert_eq!(code(notion.users_me().await), ErrorCode::ValidationError);
+    }
+}
@@ -1,11 +1,31 @@
+use crate::pagination::{paginate, FromObject, ListResponse, Pageable};
 use crate::search::SearchRequest;
 use crate::{Error, NotionApi, Object};
+use futures::Stream;
 
 const ROUTER: &str = "search";
 
 impl NotionApi {
-    /// Retrieve a user
-    pub async fn search(&self, query: SearchRequest) -> Result<Object, Error> {
+    /// Search pages and databases shared with the integration
+    pub async fn search(&self, query: SearchRequest) -> Result<ListResponse<Object>, Error> {
+        self.raw_search(query)
+            .await
+            .and_then(FromObject::from_object)
+    }
+    /// Yield every search result, fetching more as the stream is polled
+    pub fn search_stream(
+        &self,
+        query: SearchRequest,
+    ) -> impl Stream<Item = Result<Object, Error>> + '_ {
+        paginate(move |cursor| {
+            let query = query.clone().start_from(cursor);
+            async move { self.search(query).await }
+        })
+    }
+}
+
+impl NotionApi {
+    pub async fn raw_search(&self, query: SearchRequest) -> Result<Object, Error> {
         let u = format!("{}/{}", self.base_path, ROUTER);
         self.request(self.client.post(u).json(&query)).await
     }
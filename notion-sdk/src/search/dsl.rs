@@ -0,0 +1,588 @@
+//!
+//! # Filter DSL
+//!
+//! Build [`FilterCondition`] and [`DatabaseSort`] values for
+//! [`databases_query`](crate::NotionApi::databases_query) without spelling out the nested enums.
+//! Pick a property with [`prop`], its type, then a condition:
+//! only the conditions Notion supports for that type are available.
+//! Combine filters with `&` (and) and `|` (or), `&` binds tighter like in Rust.
+//! Notion takes at most [`MAX_FILTER_LEVELS`](crate::search::MAX_FILTER_LEVELS) levels of them,
+//! `((a & b) | c) & d` has three: check [`FilterCondition::levels`] of filters built in a loop.
+//!
+//! ## Examples
+//! ```rust,no_run
+//! use notion_sdk::search::dsl::{last_edited_time, prop};
+//! use notion_sdk::search::DatabaseQuery;
+//! use notion_sdk::NotionApi;
+//! # async fn run() -> Result<(), notion_sdk::error::Error> {
+//! let notion = NotionApi::new("token")?;
+//! let database_id: notion_sdk::database::id::DatabaseId = "3f8d5d7e-6c0a-4d9b-9a51-9f3c2b1e8a10".parse()?;
+//! let filter = prop("Status").status().equals("Done") & last_edited_time().past_week()
+//!     | prop("Tags").multi_select().contains("infra") & prop("Estimate").number().greater_than(3);
+//! let query = DatabaseQuery {
+//!     filter: Some(filter),
+//!     sorts: Some(vec![prop("Due").descending(), last_edited_time().ascending()]),
+//!     ..Default::default()
+//! };
+//! let pages = notion.databases_query(database_id, Some(query)).await?;
+//! # Ok(())
+//! # }
+//! ```
+use crate::pages::id::PageId;
+use crate::search::{
+    CheckboxCondition, DatabaseSort, DatabaseSortTimestamp, DateCondition, FilesCondition,
+    FilterCondition, FormulaCondition, MultiSelectCondition, NumberCondition, PeopleCondition,
+    PropertyCondition, RelationCondition, RollupCondition, SelectCondition, SortDirection,
+    TextCondition, TimestampCondition,
+};
+use crate::user::id::UserId;
+use chrono::{DateTime, Utc};
+use serde_json::Number;
+use std::ops::{BitAnd, BitOr};
+
+/// Filter or sort on the property `name`.
+pub fn prop<T: Into<String>>(name: T) -> Prop {
+    Prop { name: name.into() }
+}
+
+/// Filter or sort on when pages were created, the database needs no property for it.
+pub fn created_time() -> ConditionBuilder<DateCondition> {
+    ConditionBuilder::new(
+        SortKey::Timestamp(DatabaseSortTimestamp::CreatedTime),
+        |created_time| FilterCondition::Timestamp(TimestampCondition::CreatedTime { created_time }),
+    )
+}
+
+/// Filter or sort on when pages were last edited, the database needs no property for it.
+pub fn last_edited_time() -> ConditionBuilder<DateCondition> {
+    ConditionBuilder::new(
+        SortKey::Timestamp(DatabaseSortTimestamp::LastEditedTime),
+        |last_edited_time| {
+            FilterCondition::Timestamp(TimestampCondition::LastEditedTime { last_edited_time })
+        },
+    )
+}
+
+/// A property of the database, see [`prop`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Prop {
+    name: String,
+}
+
+/// Methods picking the type of a property
+macro_rules! item_kinds {
+    ($($(#[$doc:meta])* $method:ident: $condition:ident => $variant:ident,)+) => {
+        $(
+            $(#[$doc])*
+            pub fn $method(self) -> ConditionBuilder<$condition> {
+                self.kind(PropertyCondition::$variant)
+            }
+        )+
+    };
+}
+
+/// Types of properties which are also the types of values a rollup can collect
+macro_rules! all_item_kinds {
+    () => {
+        item_kinds! {
+            title: TextCondition => Title,
+            rich_text: TextCondition => RichText,
+            url: TextCondition => Url,
+            email: TextCondition => Email,
+            phone_number: TextCondition => PhoneNumber,
+            number: NumberCondition => Number,
+            checkbox: CheckboxCondition => Checkbox,
+            select: SelectCondition => Select,
+            status: SelectCondition => Status,
+            multi_select: MultiSelectCondition => MultiSelect,
+            date: DateCondition => Date,
+            people: PeopleCondition => People,
+            files: FilesCondition => Files,
+            relation: RelationCondition => Relation,
+        }
+    };
+}
+
+impl Prop {
+    all_item_kinds!();
+    item_kinds! {
+        created_time: DateCondition => CreatedTime,
+        last_edited_time: DateCondition => LastEditedTime,
+        created_by: PeopleCondition => CreatedBy,
+        last_edited_by: PeopleCondition => LastEditedBy,
+    }
+    pub fn formula(self) -> FormulaBuilder {
+        FormulaBuilder { name: self.name }
+    }
+    pub fn rollup(self) -> RollupBuilder {
+        RollupBuilder { name: self.name }
+    }
+    pub fn ascending(self) -> DatabaseSort {
+        SortKey::Property(self.name).sort(SortDirection::Ascending)
+    }
+    pub fn descending(self) -> DatabaseSort {
+        SortKey::Property(self.name).sort(SortDirection::Descending)
+    }
+    fn kind<C, F>(self, kind: F) -> ConditionBuilder<C>
+    where
+        F: FnOnce(C) -> PropertyCondition + 'static,
+    {
+        let name = self.name.clone();
+        ConditionBuilder::new(SortKey::Property(self.name), move |condition| {
+            FilterCondition::Property {
+                property: name,
+                condition: kind(condition),
+            }
+        })
+    }
+}
+
+/// Pick the type of the formula result, see [`Prop::formula`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct FormulaBuilder {
+    name: String,
+}
+
+impl FormulaBuilder {
+    pub fn text(self) -> ConditionBuilder<TextCondition> {
+        prop(self.name).kind(|c| PropertyCondition::Formula(FormulaCondition::Text(c)))
+    }
+    pub fn number(self) -> ConditionBuilder<NumberCondition> {
+        prop(self.name).kind(|c| PropertyCondition::Formula(FormulaCondition::Number(c)))
+    }
+    pub fn checkbox(self) -> ConditionBuilder<CheckboxCondition> {
+        prop(self.name).kind(|c| PropertyCondition::Formula(FormulaCondition::Checkbox(c)))
+    }
+    pub fn date(self) -> ConditionBuilder<DateCondition> {
+        prop(self.name).kind(|c| PropertyCondition::Formula(FormulaCondition::Date(c)))
+    }
+}
+
+/// Match the values collected by a rollup or what it computes, see [`Prop::rollup`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RollupBuilder {
+    name: String,
+}
+
+impl RollupBuilder {
+    /// Any collected value matches.
+    pub fn any(self) -> RollupItems {
+        RollupItems {
+            name: self.name,
+            quantifier: RollupCondition::Any,
+        }
+    }
+    /// Every collected value matches.
+    pub fn every(self) -> RollupItems {
+        RollupItems {
+            name: self.name,
+            quantifier: RollupCondition::Every,
+        }
+    }
+    /// No collected value matches.
+    pub fn none(self) -> RollupItems {
+        RollupItems {
+            name: self.name,
+            quantifier: RollupCondition::None,
+        }
+    }
+    /// The rollup computes a number.
+    pub fn number(self) -> ConditionBuilder<NumberCondition> {
+        prop(self.name).kind(|c| PropertyCondition::Rollup(RollupCondition::Number(c)))
+    }
+    /// The rollup computes a date.
+    pub fn date(self) -> ConditionBuilder<DateCondition> {
+        prop(self.name).kind(|c| PropertyCondition::Rollup(RollupCondition::Date(c)))
+    }
+}
+
+/// Pick the type of the values a rollup collects, see [`RollupBuilder::any`].
+#[derive(Debug, Clone)]
+pub struct RollupItems {
+    name: String,
+    quantifier: fn(Box<PropertyCondition>) -> RollupCondition,
+}
+
+impl RollupItems {
+    all_item_kinds!();
+    fn kind<C, F>(self, kind: F) -> ConditionBuilder<C>
+    where
+        F: FnOnce(C) -> PropertyCondition + 'static,
+    {
+        let quantifier = self.quantifier;
+        prop(self.name).kind(move |c| PropertyCondition::Rollup(quantifier(Box::new(kind(c)))))
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum SortKey {
+    Property(String),
+    Timestamp(DatabaseSortTimestamp),
+}
+
+impl SortKey {
+    fn sort(self, direction: SortDirection) -> DatabaseSort {
+        match self {
+            SortKey::Property(property) => DatabaseSort {
+                property: Some(property),
+                timestamp: None,
+                direction,
+            },
+            SortKey::Timestamp(timestamp) => DatabaseSort {
+                property: None,
+                timestamp: Some(timestamp),
+                direction,
+            },
+        }
+    }
+}
+
+/// The conditions available for one type of property,
+/// `C` is the condition type Notion expects for it.
+pub struct ConditionBuilder<C> {
+    sort: SortKey,
+    build: Box<dyn FnOnce(C) -> FilterCondition>,
+}
+
+impl<C> std::fmt::Debug for ConditionBuilder<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConditionBuilder")
+            .field("sort", &self.sort)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C> ConditionBuilder<C> {
+    fn new<F: FnOnce(C) -> FilterCondition + 'static>(sort: SortKey, build: F) -> Self {
+        ConditionBuilder {
+            sort,
+            build: Box::new(build),
+        }
+    }
+    /// Filter with a condition built by hand.
+    pub fn condition(self, condition: C) -> FilterCondition {
+        (self.build)(condition)
+    }
+    pub fn ascending(self) -> DatabaseSort {
+        self.sort.sort(SortDirection::Ascending)
+    }
+    pub fn descending(self) -> DatabaseSort {
+        self.sort.sort(SortDirection::Descending)
+    }
+}
+
+impl ConditionBuilder<TextCondition> {
+    pub fn equals<T: Into<String>>(self, value: T) -> FilterCondition {
+        self.condition(TextCondition::Equals(value.into()))
+    }
+    pub fn does_not_equal<T: Into<String>>(self, value: T) -> FilterCondition {
+        self.condition(TextCondition::DoesNotEqual(value.into()))
+    }
+    pub fn contains<T: Into<String>>(self, value: T) -> FilterCondition {
+        self.condition(TextCondition::Contains(value.into()))
+    }
+    pub fn does_not_contain<T: Into<String>>(self, value: T) -> FilterCondition {
+        self.condition(TextCondition::DoesNotContain(value.into()))
+    }
+    pub fn starts_with<T: Into<String>>(self, value: T) -> FilterCondition {
+        self.condition(TextCondition::StartsWith(value.into()))
+    }
+    pub fn ends_with<T: Into<String>>(self, value: T) -> FilterCondition {
+        self.condition(TextCondition::EndsWith(value.into()))
+    }
+    pub fn is_empty(self) -> FilterCondition {
+        self.condition(TextCondition::IsEmpty)
+    }
+    pub fn is_not_empty(self) -> FilterCondition {
+        self.condition(TextCondition::IsNotEmpty)
+    }
+}
+
+/// Integers convert to [`Number`], use [`Number::from_f64`] for fractions.
+impl ConditionBuilder<NumberCondition> {
+    pub fn equals<T: Into<Number>>(self, value: T) -> FilterCondition {
+        self.condition(NumberCondition::Equals(value.into()))
+    }
+    pub fn does_not_equal<T: Into<Number>>(self, value: T) -> FilterCondition {
+        self.condition(NumberCondition::DoesNotEqual(value.into()))
+    }
+    pub fn greater_than<T: Into<Number>>(self, value: T) -> FilterCondition {
+        self.condition(NumberCondition::GreaterThan(value.into()))
+    }
+    pub fn less_than<T: Into<Number>>(self, value: T) -> FilterCondition {
+        self.condition(NumberCondition::LessThan(value.into()))
+    }
+    pub fn greater_than_or_equal_to<T: Into<Number>>(self, value: T) -> FilterCondition {
+        self.condition(NumberCondition::GreaterThanOrEqualTo(value.into()))
+    }
+    pub fn less_than_or_equal_to<T: Into<Number>>(self, value: T) -> FilterCondition {
+        self.condition(NumberCondition::LessThanOrEqualTo(value.into()))
+    }
+    pub fn is_empty(self) -> FilterCondition {
+        self.condition(NumberCondition::IsEmpty)
+    }
+    pub fn is_not_empty(self) -> FilterCondition {
+        self.condition(NumberCondition::IsNotEmpty)
+    }
+}
+
+impl ConditionBuilder<CheckboxCondition> {
+    pub fn equals(self, value: bool) -> FilterCondition {
+        self.condition(CheckboxCondition::Equals(value))
+    }
+    pub fn does_not_equal(self, value: bool) -> FilterCondition {
+        self.condition(CheckboxCondition::DoesNotEqual(value))
+    }
+}
+
+impl ConditionBuilder<SelectCondition> {
+    pub fn equals<T: Into<String>>(self, value: T) -> FilterCondition {
+        self.condition(SelectCondition::Equals(value.into()))
+    }
+    pub fn does_not_equal<T: Into<String>>(self, value: T) -> FilterCondition {
+        self.condition(SelectCondition::DoesNotEqual(value.into()))
+    }
+    pub fn is_empty(self) -> FilterCondition {
+        self.condition(SelectCondition::IsEmpty)
+    }
+    pub fn is_not_empty(self) -> FilterCondition {
+        self.condition(SelectCondition::IsNotEmpty)
+    }
+}
+
+impl ConditionBuilder<MultiSelectCondition> {
+    pub fn contains<T: Into<String>>(self, value: T) -> FilterCondition {
+        self.condition(MultiSelectCondition::Contains(value.into()))
+    }
+    pub fn does_not_contain<T: Into<String>>(self, value: T) -> FilterCondition {
+        self.condition(MultiSelectCondition::DoesNotContain(value.into()))
+    }
+    pub fn is_empty(self) -> FilterCondition {
+        self.condition(MultiSelectCondition::IsEmpty)
+    }
+    pub fn is_not_empty(self) -> FilterCondition {
+        self.condition(MultiSelectCondition::IsNotEmpty)
+    }
+}
+
+impl ConditionBuilder<DateCondition> {
+    pub fn equals(self, value: DateTime<Utc>) -> FilterCondition {
+        self.condition(DateCondition::Equals(value))
+    }
+    pub fn before(self, value: DateTime<Utc>) -> FilterCondition {
+        self.condition(DateCondition::Before(value))
+    }
+    pub fn after(self, value: DateTime<Utc>) -> FilterCondition {
+        self.condition(DateCondition::After(value))
+    }
+    pub fn on_or_before(self, value: DateTime<Utc>) -> FilterCondition {
+        self.condition(DateCondition::OnOrBefore(value))
+    }
+    pub fn on_or_after(self, value: DateTime<Utc>) -> FilterCondition {
+        self.condition(DateCondition::OnOrAfter(value))
+    }
+    pub fn is_empty(self) -> FilterCondition {
+        self.condition(DateCondition::IsEmpty)
+    }
+    pub fn is_not_empty(self) -> FilterCondition {
+        self.condition(DateCondition::IsNotEmpty)
+    }
+    pub fn past_week(self) -> FilterCondition {
+        self.condition(DateCondition::PastWeek)
+    }
+    pub fn past_month(self) -> FilterCondition {
+        self.condition(DateCondition::PastMonth)
+    }
+    pub fn past_year(self) -> FilterCondition {
+        self.condition(DateCondition::PastYear)
+    }
+    pub fn next_week(self) -> FilterCondition {
+        self.condition(DateCondition::NextWeek)
+    }
+    pub fn next_month(self) -> FilterCondition {
+        self.condition(DateCondition::NextMonth)
+    }
+    pub fn next_year(self) -> FilterCondition {
+        self.condition(DateCondition::NextYear)
+    }
+}
+
+impl ConditionBuilder<PeopleCondition> {
+    pub fn contains(self, user: UserId) -> FilterCondition {
+        self.condition(PeopleCondition::Contains(user))
+    }
+    pub fn does_not_contain(self, user: UserId) -> FilterCondition {
+        self.condition(PeopleCondition::DoesNotContain(user))
+    }
+    pub fn is_empty(self) -> FilterCondition {
+        self.condition(PeopleCondition::IsEmpty)
+    }
+    pub fn is_not_empty(self) -> FilterCondition {
+        self.condition(PeopleCondition::IsNotEmpty)
+    }
+}
+
+impl ConditionBuilder<FilesCondition> {
+    pub fn is_empty(self) -> FilterCondition {
+        self.condition(FilesCondition::IsEmpty)
+    }
+    pub fn is_not_empty(self) -> FilterCondition {
+        self.condition(FilesCondition::IsNotEmpty)
+    }
+}
+
+impl ConditionBuilder<RelationCondition> {
+    pub fn contains(self, page: PageId) -> FilterCondition {
+        self.condition(RelationCondition::Contains(page))
+    }
+    pub fn does_not_contain(self, page: PageId) -> FilterCondition {
+        self.condition(RelationCondition::DoesNotContain(page))
+    }
+    pub fn is_empty(self) -> FilterCondition {
+        self.condition(RelationCondition::IsEmpty)
+    }
+    pub fn is_not_empty(self) -> FilterCondition {
+        self.condition(RelationCondition::IsNotEmpty)
+    }
+}
+
+/// Both filters match, nested `and` filters are flattened.
+impl BitAnd for FilterCondition {
+    type Output = FilterCondition;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        let mut and = match self {
+            FilterCondition::And { and } => and,
+            filter => vec![filter],
+        };
+        match rhs {
+            FilterCondition::And { and: rhs } => and.extend(rhs),
+            filter => and.push(filter),
+        }
+        FilterCondition::And { and }
+    }
+}
+
+/// Either filter matches, nested `or` filters are flattened.
+impl BitOr for FilterCondition {
+    type Output = FilterCondition;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        let mut or = match self {
+            FilterCondition::Or { or } => or,
+            filter => vec![filter],
+        };
+        match rhs {
+            FilterCondition::Or { or: rhs } => or.extend(rhs),
+            filter => or.push(filter),
+        }
+        FilterCondition::Or { or }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::MAX_FILTER_LEVELS;
+    use serde_json::{json, Value};
+
+    fn to_json<T: serde::Serialize>(value: T) -> Value {
+        serde_json::to_value(value).unwrap()
+    }
+
+    fn done() -> FilterCondition {
+        prop("Done").checkbox().equals(true)
+    }
+
+    fn urgent() -> FilterCondition {
+        prop("Priority").select().equals("Urgent")
+    }
+
+    fn tagged() -> FilterCondition {
+        prop("Tags").multi_select().contains("infra")
+    }
+
+    #[test]
+    fn conditions_serialize_under_their_property_type() {
+        assert_eq!(
+            to_json(prop("Estimate").number().greater_than(3)),
+            json!({"property": "Estimate", "number": {"greater_than": 3}})
+        );
+        assert_eq!(
+            to_json(prop("Name").title().is_empty()),
+            json!({"property": "Name", "title": {"is_empty": true}})
+        );
+        assert_eq!(
+            to_json(last_edited_time().past_week()),
+            json!({"timestamp": "last_edited_time", "last_edited_time": {"past_week": {}}})
+        );
+        assert_eq!(
+            to_json(prop("Score").formula().number().equals(1)),
+            json!({"property": "Score", "formula": {"number": {"equals": 1}}})
+        );
+        assert_eq!(
+            to_json(prop("Owners").rollup().any().rich_text().contains("bot")),
+            json!({"property": "Owners", "rollup": {"any": {"rich_text": {"contains": "bot"}}}})
+        );
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        assert_eq!(
+            to_json(done() & urgent() | tagged()),
+            json!({"or": [
+                {"and": [
+                    {"property": "Done", "checkbox": {"equals": true}},
+                    {"property": "Priority", "select": {"equals": "Urgent"}},
+                ]},
+                {"property": "Tags", "multi_select": {"contains": "infra"}},
+            ]})
+        );
+    }
+
+    #[test]
+    fn chained_operators_are_flattened() {
+        assert_eq!(
+            done() & urgent() & tagged(),
+            FilterCondition::And {
+                and: vec![done(), urgent(), tagged()]
+            }
+        );
+        assert_eq!(
+            done() | (urgent() | tagged()),
+            FilterCondition::Or {
+                or: vec![done(), urgent(), tagged()]
+            }
+        );
+        assert_eq!(
+            (done() & urgent()) & (tagged() & done()),
+            FilterCondition::And {
+                and: vec![done(), urgent(), tagged(), done()]
+            }
+        );
+    }
+
+    #[test]
+    fn levels_count_nested_compound_filters() {
+        assert_eq!(done().levels(), 0);
+        assert_eq!((done() & urgent() & tagged()).levels(), 1);
+        assert_eq!((done() & urgent() | tagged()).levels(), MAX_FILTER_LEVELS);
+        assert_eq!(((done() & urgent() | tagged()) & done()).levels(), 3);
+    }
+
+    #[test]
+    fn sorts_name_a_property_or_a_timestamp() {
+        assert_eq!(
+            to_json(prop("Due").descending()),
+            json!({"property": "Due", "direction": "descending"})
+        );
+        assert_eq!(
+            to_json(created_time().ascending()),
+            json!({"timestamp": "created_time", "direction": "ascending"})
+        );
+        assert_eq!(
+            to_json(prop("Estimate").number().ascending()),
+            json!({"property": "Estimate", "direction": "ascending"})
+        );
+    }
+}
@@ -0,0 +1,795 @@
+//!
+//! # Local Evaluation
+//!
+//! Apply the filters and sorts of a [`DatabaseQuery`] to pages which were already fetched,
+//! with the same semantics as Notion:
+//! - `contains`, `starts_with` and `ends_with` ignore case, `equals` does not
+//! - dates are compared by day in UTC, a date range matches when any of its days does
+//! - `past_week`, `next_month`, ... are relative to the evaluator's `now`
+//! - a filter on a property the page does not have, or of another type, does not match
+//! - empty values sort last in both directions
+//! - `select` and `multi_select` sort by the order of the options in the schema of the database,
+//!   `status` by group first and option second
+//!
+//! ## Examples
+//! ```rust,no_run
+//! use notion_sdk::pagination::collect_all;
+//! use notion_sdk::search::dsl::{last_edited_time, prop};
+//! use notion_sdk::search::local::LocalEvaluator;
+//! use notion_sdk::NotionApi;
+//! # async fn run() -> Result<(), notion_sdk::error::Error> {
+//! let notion = NotionApi::new("token")?;
+//! let database_id: notion_sdk::database::id::DatabaseId = "3f8d5d7e-6c0a-4d9b-9a51-9f3c2b1e8a10".parse()?;
+//! let database = notion.databases_retrieve(database_id.clone()).await?;
+//! let pages = collect_all(notion.databases_query_stream(database_id, None), None).await?;
+//! let evaluator = LocalEvaluator::new();
+//! let done = evaluator.filter(
+//!     &(prop("Status").status().equals("Done") & last_edited_time().past_week()),
+//!     pages.clone(),
+//! );
+//! let mut by_due = pages;
+//! evaluator.sort(&database, &[prop("Due").ascending()], &mut by_due);
+//! # Ok(())
+//! # }
+//! ```
+use crate::database::date::{DateOrDateTime, DateValue, FormulaResultValue};
+use crate::database::properties::{PropertyConfiguration, PropertyValue};
+use crate::database::rollup::{RollupPropertyValue, RollupValue};
+use crate::database::select::SelectedValue;
+use crate::database::Database;
+use crate::pages::id::PageId;
+use crate::pages::Page;
+use crate::plain_text::rich_text_to_plain_text;
+use crate::search::{
+    CheckboxCondition, DatabaseQuery, DatabaseSort, DatabaseSortTimestamp, DateCondition,
+    FilesCondition, FilterCondition, FormulaCondition, MultiSelectCondition, NumberCondition,
+    PeopleCondition, PropertyCondition, RelationCondition, RollupCondition, SelectCondition,
+    SortDirection, TextCondition, TimestampCondition,
+};
+use crate::user::id::UserId;
+use crate::user::User;
+use chrono::{DateTime, Duration, Months, NaiveDate, TimeZone, Utc};
+use std::cmp::Ordering;
+
+/// Filters and sorts pages without calling the API.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct LocalEvaluator {
+    now: DateTime<Utc>,
+}
+
+impl Default for LocalEvaluator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LocalEvaluator {
+    /// Relative dates are resolved against the current time.
+    pub fn new() -> Self {
+        Self::at(Utc::now())
+    }
+    /// Relative dates are resolved against `now`.
+    pub fn at(now: DateTime<Utc>) -> Self {
+        LocalEvaluator { now }
+    }
+    /// Whether Notion would return the page for this filter.
+    pub fn matches(&self, filter: &FilterCondition, page: &Page) -> bool {
+        match filter {
+            FilterCondition::Property {
+                property,
+                condition,
+            } => page
+                .get_property(property)
+                .is_some_and(|value| self.condition(condition, Value::of(value))),
+            FilterCondition::Timestamp(TimestampCondition::CreatedTime { created_time }) => {
+                self.date(created_time, Some(Span::at(page.created_time)))
+            }
+            FilterCondition::Timestamp(TimestampCondition::LastEditedTime { last_edited_time }) => {
+                self.date(last_edited_time, Some(Span::at(page.last_edited_time)))
+            }
+            FilterCondition::And { and } => and.iter().all(|filter| self.matches(filter, page)),
+            FilterCondition::Or { or } => or.iter().any(|filter| self.matches(filter, page)),
+        }
+    }
+    /// Keep the pages matching the filter.
+    pub fn filter(&self, filter: &FilterCondition, mut pages: Vec<Page>) -> Vec<Page> {
+        pages.retain(|page| self.matches(filter, page));
+        pages
+    }
+    /// Sort by the first sort, then the next one for ties, keeping the order of equal pages.
+    ///
+    /// Options are ranked by their position in the schema of `database`, the database the pages come from.
+    pub fn sort(&self, database: &Database, sorts: &[DatabaseSort], pages: &mut [Page]) {
+        pages.sort_by(|a, b| {
+            sorts
+                .iter()
+                .map(|sort| compare(database, sort, a, b))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+    }
+    /// Filter and sort like [`databases_query`](crate::NotionApi::databases_query), paging is ignored.
+    pub fn query(&self, database: &Database, query: &DatabaseQuery, pages: Vec<Page>) -> Vec<Page> {
+        let mut pages = match &query.filter {
+            Some(filter) => self.filter(filter, pages),
+            None => pages,
+        };
+        if let Some(sorts) = &query.sorts {
+            self.sort(database, sorts, &mut pages);
+        }
+        pages
+    }
+
+    fn condition(&self, condition: &PropertyCondition, value: Value) -> bool {
+        match (condition, value) {
+            (
+                PropertyCondition::Title(condition)
+                | PropertyCondition::RichText(condition)
+                | PropertyCondition::Url(condition)
+                | PropertyCondition::Email(condition)
+                | PropertyCondition::PhoneNumber(condition),
+                Value::Text(text),
+            ) => self.text(condition, &text),
+            (PropertyCondition::Number(condition), Value::Number(number)) => {
+                self.number(condition, number)
+            }
+            (PropertyCondition::Checkbox(condition), Value::Checkbox(checked)) => {
+                self.checkbox(condition, checked)
+            }
+            (
+                PropertyCondition::Select(condition) | PropertyCondition::Status(condition),
+                Value::Select(selected),
+            ) => self.select(condition, selected),
+            (PropertyCondition::MultiSelect(condition), Value::MultiSelect(selected)) => {
+                self.multi_select(condition, &selected)
+            }
+            (
+                PropertyCondition::Date(condition)
+                | PropertyCondition::CreatedTime(condition)
+                | PropertyCondition::LastEditedTime(condition),
+                Value::Date(span),
+            ) => self.date(condition, span),
+            (
+                PropertyCondition::People(condition)
+                | PropertyCondition::CreatedBy(condition)
+                | PropertyCondition::LastEditedBy(condition),
+                Value::People(people),
+            ) => self.people(condition, &people),
+            (PropertyCondition::Files(condition), Value::Files(count)) => match condition {
+                FilesCondition::IsEmpty => count == 0,
+                FilesCondition::IsNotEmpty => count > 0,
+            },
+            (PropertyCondition::Relation(condition), Value::Relation(pages)) => {
+                self.relation(condition, &pages)
+            }
+            (PropertyCondition::Formula(condition), Value::Formula(result)) => {
+                self.formula(condition, result)
+            }
+            (PropertyCondition::Rollup(condition), Value::Rollup(rollup)) => {
+                self.rollup(condition, rollup)
+            }
+            _ => false,
+        }
+    }
+
+    fn text(&self, condition: &TextCondition, text: &str) -> bool {
+        let lowercase = text.to_lowercase();
+        match condition {
+            TextCondition::Equals(value) => text == value,
+            TextCondition::DoesNotEqual(value) => text != value,
+            TextCondition::Contains(value) => lowercase.contains(&value.to_lowercase()),
+            TextCondition::DoesNotContain(value) => !lowercase.contains(&value.to_lowercase()),
+            TextCondition::StartsWith(value) => lowercase.starts_with(&value.to_lowercase()),
+            TextCondition::EndsWith(value) => lowercase.ends_with(&value.to_lowercase()),
+            TextCondition::IsEmpty => text.is_empty(),
+            TextCondition::IsNotEmpty => !text.is_empty(),
+        }
+    }
+
+    fn number(&self, condition: &NumberCondition, number: Option<f64>) -> bool {
+        let compare = |value: &serde_json::Number, test: fn(f64, f64) -> bool| {
+            number
+                .zip(value.as_f64())
+                .is_some_and(|(number, value)| test(number, value))
+        };
+        match condition {
+            NumberCondition::Equals(value) => compare(value, |a, b| a == b),
+            NumberCondition::DoesNotEqual(value) => !compare(value, |a, b| a == b),
+            NumberCondition::GreaterThan(value) => compare(value, |a, b| a > b),
+            NumberCondition::LessThan(value) => compare(value, |a, b| a < b),
+            NumberCondition::GreaterThanOrEqualTo(value) => compare(value, |a, b| a >= b),
+            NumberCondition::LessThanOrEqualTo(value) => compare(value, |a, b| a <= b),
+            NumberCondition::IsEmpty => number.is_none(),
+            NumberCondition::IsNotEmpty => number.is_some(),
+        }
+    }
+
+    fn checkbox(&self, condition: &CheckboxCondition, checked: bool) -> bool {
+        match condition {
+            CheckboxCondition::Equals(value) => checked == *value,
+            CheckboxCondition::DoesNotEqual(value) => checked != *value,
+        }
+    }
+
+    fn select(&self, condition: &SelectCondition, selected: Option<&str>) -> bool {
+        match condition {
+            SelectCondition::Equals(value) => selected == Some(value.as_str()),
+            SelectCondition::DoesNotEqual(value) => selected != Some(value.as_str()),
+            SelectCondition::IsEmpty => selected.is_none(),
+            SelectCondition::IsNotEmpty => selected.is_some(),
+        }
+    }
+
+    fn multi_select(&self, condition: &MultiSelectCondition, selected: &[&str]) -> bool {
+        match condition {
+            MultiSelectCondition::Contains(value) => selected.contains(&value.as_str()),
+            MultiSelectCondition::DoesNotContain(value) => !selected.contains(&value.as_str()),
+            MultiSelectCondition::IsEmpty => selected.is_empty(),
+            MultiSelectCondition::IsNotEmpty => !selected.is_empty(),
+        }
+    }
+
+    fn date(&self, condition: &DateCondition, span: Option<Span>) -> bool {
+        let today = self.now.date_naive();
+        let Some(span) = span else {
+            return matches!(condition, DateCondition::IsEmpty);
+        };
+        let (start, end) = span.days();
+        let within = |from: NaiveDate, to: NaiveDate| start <= to && end >= from;
+        match condition {
+            DateCondition::Equals(day) => within(day.date_naive(), day.date_naive()),
+            DateCondition::Before(day) => start < day.date_naive(),
+            DateCondition::After(day) => end > day.date_naive(),
+            DateCondition::OnOrBefore(day) => start <= day.date_naive(),
+            DateCondition::OnOrAfter(day) => end >= day.date_naive(),
+            DateCondition::IsEmpty => false,
+            DateCondition::IsNotEmpty => true,
+            DateCondition::PastWeek => within(today - Duration::days(7), today),
+            DateCondition::PastMonth => within(months_before(today, 1), today),
+            DateCondition::PastYear => within(months_before(today, 12), today),
+            DateCondition::NextWeek => within(today, today + Duration::days(7)),
+            DateCondition::NextMonth => within(today, months_after(today, 1)),
+            DateCondition::NextYear => within(today, months_after(today, 12)),
+        }
+    }
+
+    fn people(&self, condition: &PeopleCondition, people: &[&User]) -> bool {
+        let contains = |id| people.iter().any(|user| user_id(user) == id);
+        match condition {
+            PeopleCondition::Contains(id) => contains(id),
+            PeopleCondition::DoesNotContain(id) => !contains(id),
+            PeopleCondition::IsEmpty => people.is_empty(),
+            PeopleCondition::IsNotEmpty => !people.is_empty(),
+        }
+    }
+
+    fn relation(&self, condition: &RelationCondition, pages: &[&PageId]) -> bool {
+        match condition {
+            RelationCondition::Contains(id) => pages.contains(&id),
+            RelationCondition::DoesNotContain(id) => !pages.contains(&id),
+            RelationCondition::IsEmpty => pages.is_empty(),
+            RelationCondition::IsNotEmpty => !pages.is_empty(),
+        }
+    }
+
+    fn formula(&self, condition: &FormulaCondition, result: &FormulaResultValue) -> bool {
+        match (condition, result) {
+            (FormulaCondition::Text(condition), FormulaResultValue::String { string }) => {
+                self.text(condition, string.as_deref().unwrap_or_default())
+            }
+            (FormulaCondition::Number(condition), FormulaResultValue::Number { number }) => {
+                self.number(condition, number.as_ref().and_then(|n| n.as_f64()))
+            }
+            (FormulaCondition::Checkbox(condition), FormulaResultValue::Boolean { boolean }) => {
+                self.checkbox(condition, boolean.unwrap_or_default())
+            }
+            (FormulaCondition::Date(condition), FormulaResultValue::Date { date }) => {
+                self.date(condition, date.as_ref().map(Span::of))
+            }
+            _ => false,
+        }
+    }
+
+    fn rollup(&self, condition: &RollupCondition, rollup: Option<&RollupValue>) -> bool {
+        match (condition, rollup) {
+            (RollupCondition::Any(condition), Some(RollupValue::Array { array })) => array
+                .iter()
+                .any(|item| self.condition(condition, Value::of_item(item))),
+            (RollupCondition::Every(condition), Some(RollupValue::Array { array })) => array
+                .iter()
+                .all(|item| self.condition(condition, Value::of_item(item))),
+            (RollupCondition::None(condition), Some(RollupValue::Array { array })) => !array
+                .iter()
+                .any(|item| self.condition(condition, Value::of_item(item))),
+            (RollupCondition::Number(condition), Some(RollupValue::Number { number })) => {
+                self.number(condition, number.as_ref().and_then(|n| n.as_f64()))
+            }
+            (RollupCondition::Date(condition), Some(RollupValue::Date { date })) => {
+                self.date(condition, date.map(Span::at))
+            }
+            _ => false,
+        }
+    }
+}
+
+/// A property value reduced to what filters and sorts look at
+enum Value<'a> {
+    Text(String),
+    Number(Option<f64>),
+    Checkbox(bool),
+    Select(Option<&'a str>),
+    MultiSelect(Vec<&'a str>),
+    Date(Option<Span>),
+    People(Vec<&'a User>),
+    Files(usize),
+    Relation(Vec<&'a PageId>),
+    Formula(&'a FormulaResultValue),
+    Rollup(Option<&'a RollupValue>),
+}
+
+impl<'a> Value<'a> {
+    fn of(value: &'a PropertyValue) -> Self {
+        match value {
+            PropertyValue::Title { title: text, .. }
+            | PropertyValue::Text {
+                rich_text: text, ..
+            } => Value::Text(rich_text_to_plain_text(text)),
+            PropertyValue::Url { url: text, .. }
+            | PropertyValue::Email { email: text, .. }
+            | PropertyValue::PhoneNumber {
+                phone_number: text, ..
+            } => Value::Text(text.clone().unwrap_or_default()),
+            PropertyValue::Number { number, .. } => {
+                Value::Number(number.as_ref().and_then(|n| n.as_f64()))
+            }
+            PropertyValue::Checkbox { checkbox, .. } => Value::Checkbox(*checkbox),
+            PropertyValue::Select {
+                select: selected, ..
+            }
+            | PropertyValue::Status {
+                status: selected, ..
+            } => Value::Select(selected.as_ref().and_then(option_name)),
+            PropertyValue::MultiSelect { multi_select, .. } => {
+                Value::MultiSelect(multi_options(multi_select.as_deref()))
+            }
+            PropertyValue::Date { date, .. } => Value::Date(date.as_ref().map(Span::of)),
+            PropertyValue::CreatedTime { created_time, .. } => {
+                Value::Date(Some(Span::at(*created_time)))
+            }
+            PropertyValue::LastEditedTime {
+                last_edited_time, ..
+            } => Value::Date(Some(Span::at(*last_edited_time))),
+            PropertyValue::People { people, .. } => Value::People(people.iter().collect()),
+            PropertyValue::CreatedBy { created_by, .. } => Value::People(vec![created_by]),
+            PropertyValue::LastEditedBy { last_edited_by, .. } => {
+                Value::People(vec![last_edited_by])
+            }
+            PropertyValue::Files { files, .. } => Value::Files(files.as_ref().map_or(0, Vec::len)),
+            PropertyValue::Relation { relation, .. } => Value::Relation(
+                relation
+                    .iter()
+                    .flatten()
+                    .map(|relation| &relation.id)
+                    .collect(),
+            ),
+            PropertyValue::Formula { formula, .. } => Value::Formula(formula),
+            PropertyValue::Rollup { rollup, .. } => Value::Rollup(rollup.as_ref()),
+        }
+    }
+
+    fn of_item(value: &'a RollupPropertyValue) -> Self {
+        match value {
+            RollupPropertyValue::Text { rich_text } => {
+                Value::Text(rich_text_to_plain_text(rich_text))
+            }
+            RollupPropertyValue::Url { url: text } | RollupPropertyValue::Email { email: text } => {
+                Value::Text(text.clone().unwrap_or_default())
+            }
+            RollupPropertyValue::PhoneNumber { phone_number } => Value::Text(phone_number.clone()),
+            RollupPropertyValue::Number { number } => {
+                Value::Number(number.as_ref().and_then(|n| n.as_f64()))
+            }
+            RollupPropertyValue::Checkbox { checkbox } => Value::Checkbox(*checkbox),
+            RollupPropertyValue::Select { select: selected }
+            | RollupPropertyValue::Status { status: selected } => {
+                Value::Select(selected.as_ref().and_then(option_name))
+            }
+            RollupPropertyValue::MultiSelect { multi_select } => {
+                Value::MultiSelect(multi_options(multi_select.as_deref()))
+            }
+            RollupPropertyValue::Date { date } => Value::Date(date.as_ref().map(Span::of)),
+            RollupPropertyValue::CreatedTime { created_time } => {
+                Value::Date(Some(Span::at(*created_time)))
+            }
+            RollupPropertyValue::LastEditedTime { last_edited_time } => {
+                Value::Date(Some(Span::at(*last_edited_time)))
+            }
+            RollupPropertyValue::People { people } => Value::People(people.iter().collect()),
+            RollupPropertyValue::CreatedBy { created_by } => Value::People(vec![created_by]),
+            RollupPropertyValue::LastEditedBy { last_edited_by } => {
+                Value::People(vec![last_edited_by])
+            }
+            RollupPropertyValue::Files { files } => {
+                Value::Files(files.as_ref().map_or(0, Vec::len))
+            }
+            RollupPropertyValue::Relation { relation } => Value::Relation(
+                relation
+                    .iter()
+                    .flatten()
+                    .map(|relation| &relation.id)
+                    .collect(),
+            ),
+            RollupPropertyValue::Formula { formula } => Value::Formula(formula),
+            RollupPropertyValue::Rollup { rollup } => Value::Rollup(rollup.as_ref()),
+        }
+    }
+
+    /// what the value sorts by, `None` for empty values,
+    /// options sort by their position in `schema` and by name without one
+    fn sort_key(&self, schema: Option<&PropertyConfiguration>) -> Option<SortKey> {
+        match self {
+            Value::Text(text) => (!text.is_empty()).then(|| SortKey::Text(text.to_lowercase())),
+            Value::Number(number) => number.map(SortKey::Number),
+            Value::Checkbox(checked) => Some(SortKey::Checkbox(*checked)),
+            Value::Select(selected) => selected.map(|name| option_key(schema, &[name])),
+            Value::MultiSelect(selected) => {
+                (!selected.is_empty()).then(|| option_key(schema, selected))
+            }
+            Value::Date(span) => span.map(|span| SortKey::Date(span.start)),
+            Value::People(people) => people
+                .first()
+                .map(|user| SortKey::Text(user_name(user).unwrap_or_default().to_lowercase())),
+            Value::Files(count) => (*count > 0).then_some(SortKey::Number(*count as f64)),
+            Value::Relation(pages) => {
+                (!pages.is_empty()).then_some(SortKey::Number(pages.len() as f64))
+            }
+            Value::Formula(result) => match result {
+                FormulaResultValue::String { string } => string
+                    .as_deref()
+                    .filter(|string| !string.is_empty())
+                    .map(|string| SortKey::Text(string.to_lowercase())),
+                FormulaResultValue::Number { number } => number
+                    .as_ref()
+                    .and_then(|n| n.as_f64())
+                    .map(SortKey::Number),
+                FormulaResultValue::Boolean { boolean } => boolean.map(SortKey::Checkbox),
+                FormulaResultValue::Date { date } => date
+                    .as_ref()
+                    .map(|date| SortKey::Date(Span::of(date).start)),
+            },
+            Value::Rollup(rollup) => match rollup {
+                Some(RollupValue::Number { number }) => number
+                    .as_ref()
+                    .and_then(|n| n.as_f64())
+                    .map(SortKey::Number),
+                Some(RollupValue::Date { date }) => date.map(SortKey::Date),
+                Some(RollupValue::Array { array }) => array
+                    .first()
+                    .and_then(|item| Value::of_item(item).sort_key(None)),
+                None => None,
+            },
+        }
+    }
+}
+
+#[derive(PartialEq, PartialOrd)]
+enum SortKey {
+    Checkbox(bool),
+    Number(f64),
+    Text(String),
+    Date(DateTime<Utc>),
+    /// group and option position of every selected option
+    Options(Vec<(usize, usize)>),
+}
+
+/// Options missing from the schema sort after the known ones, by name without a schema.
+fn option_key(schema: Option<&PropertyConfiguration>, names: &[&str]) -> SortKey {
+    let position = |name: &str| match schema {
+        Some(
+            PropertyConfiguration::Select { select, .. }
+            | PropertyConfiguration::MultiSelect {
+                multi_select: select,
+                ..
+            },
+        ) => select
+            .options
+            .iter()
+            .position(|option| option.name == name)
+            .map(|index| (0, index)),
+        Some(PropertyConfiguration::Status { status, .. }) => status
+            .options
+            .iter()
+            .position(|option| option.name == name)
+            .map(|index| {
+                let id = &status.options[index].id;
+                let group = status
+                    .groups
+                    .iter()
+                    .position(|group| group.option_ids.contains(id))
+                    .unwrap_or(status.groups.len());
+                (group, index)
+            }),
+        _ => None,
+    };
+    match schema {
+        Some(
+            PropertyConfiguration::Select { .. }
+            | PropertyConfiguration::MultiSelect { .. }
+            | PropertyConfiguration::Status { .. },
+        ) => SortKey::Options(
+            names
+                .iter()
+                .map(|name| position(name).unwrap_or((usize::MAX, usize::MAX)))
+                .collect(),
+        ),
+        _ => SortKey::Text(names[0].to_lowercase()),
+    }
+}
+
+fn compare(database: &Database, sort: &DatabaseSort, a: &Page, b: &Page) -> Ordering {
+    let key = |page: &Page| match (&sort.property, &sort.timestamp) {
+        (Some(property), _) => page
+            .get_property(property)
+            .and_then(|value| Value::of(value).sort_key(database.find_property(property))),
+        (None, Some(DatabaseSortTimestamp::CreatedTime)) => Some(SortKey::Date(page.created_time)),
+        (None, Some(DatabaseSortTimestamp::LastEditedTime)) => {
+            Some(SortKey::Date(page.last_edited_time))
+        }
+        (None, None) => None,
+    };
+    match (key(a), key(b)) {
+        (Some(a), Some(b)) => {
+            let ordering = a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+            match sort.direction {
+                SortDirection::Ascending => ordering,
+                SortDirection::Descending => ordering.reverse(),
+            }
+        }
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Start and end of a date value, dates without a time start at midnight UTC
+#[derive(Clone, Copy)]
+struct Span {
+    start: DateTime<Utc>,
+    end: Option<DateTime<Utc>>,
+}
+
+impl Span {
+    fn at(time: DateTime<Utc>) -> Self {
+        Span {
+            start: time,
+            end: None,
+        }
+    }
+    fn of(date: &DateValue) -> Self {
+        Span {
+            start: instant(&date.start),
+            end: date.end.as_ref().map(instant),
+        }
+    }
+    /// first and last day
+    fn days(&self) -> (NaiveDate, NaiveDate) {
+        let start = self.start.date_naive();
+        (start, self.end.map_or(start, |end| end.date_naive()))
+    }
+}
+
+fn instant(date: &DateOrDateTime) -> DateTime<Utc> {
+    match date {
+        DateOrDateTime::Date(date) => {
+            Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap_or_default())
+        }
+        DateOrDateTime::DateTime(time) => *time,
+    }
+}
+
+fn months_before(day: NaiveDate, months: u32) -> NaiveDate {
+    day.checked_sub_months(Months::new(months)).unwrap_or(day)
+}
+
+fn months_after(day: NaiveDate, months: u32) -> NaiveDate {
+    day.checked_add_months(Months::new(months)).unwrap_or(day)
+}
+
+fn option_name(selected: &SelectedValue) -> Option<&str> {
+    selected.name.as_deref()
+}
+
+fn multi_options(selected: Option<&[SelectedValue]>) -> Vec<&str> {
+    selected
+        .unwrap_or_default()
+        .iter()
+        .filter_map(option_name)
+        .collect()
+}
+
+fn user_id(user: &User) -> &UserId {
+    match user {
+        User::Person { common, .. } | User::Bot { common, .. } => &common.id,
+    }
+}
+
+fn user_name(user: &User) -> Option<&str> {
+    match user {
+        User::Person { common, .. } | User::Bot { common, .. } => common.name.as_deref(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{database, day, page};
+    use crate::search::dsl::prop;
+    use serde_json::{json, Value};
+
+    fn text(content: &str) -> Value {
+        json!({"id": "a", "type": "rich_text", "rich_text": [{
+            "type": "text",
+            "text": {"content": content, "link": null},
+            "plain_text": content,
+        }]})
+    }
+
+    fn date(start: &str, end: Option<&str>) -> Value {
+        json!({"id": "b", "type": "date", "date": {"start": start, "end": end, "time_zone": null}})
+    }
+
+    fn ids(pages: &[Page]) -> Vec<&PageId> {
+        pages.iter().map(|page| &page.id).collect()
+    }
+
+    #[test]
+    fn contains_ignores_case_and_equals_does_not() {
+        let evaluator = LocalEvaluator::new();
+        let page = page(1, json!({"Notes": text("Infra Work")}));
+        assert!(evaluator.matches(&prop("Notes").rich_text().contains("infra"), &page));
+        assert!(evaluator.matches(&prop("Notes").rich_text().ends_with("WORK"), &page));
+        assert!(!evaluator.matches(&prop("Notes").rich_text().equals("infra work"), &page));
+        assert!(evaluator.matches(&prop("Notes").rich_text().equals("Infra Work"), &page));
+    }
+
+    #[test]
+    fn empty_checks() {
+        let evaluator = LocalEvaluator::new();
+        let empty = page(
+            1,
+            json!({
+                "Notes": {"id": "a", "type": "rich_text", "rich_text": []},
+                "Due": {"id": "b", "type": "date", "date": null},
+                "Estimate": {"id": "c", "type": "number", "number": null},
+                "Priority": {"id": "d", "type": "select", "select": null},
+            }),
+        );
+        let filled = page(
+            2,
+            json!({
+                "Notes": text("x"),
+                "Due": date("2026-10-10", None),
+                "Estimate": {"id": "c", "type": "number", "number": 3},
+                "Priority": {"id": "d", "type": "select", "select": {"name": "High", "color": "red"}},
+            }),
+        );
+        for filter in [
+            prop("Notes").rich_text().is_empty(),
+            prop("Due").date().is_empty(),
+            prop("Estimate").number().is_empty(),
+            prop("Priority").select().is_empty(),
+        ] {
+            assert!(evaluator.matches(&filter, &empty), "{filter:?}");
+            assert!(!evaluator.matches(&filter, &filled), "{filter:?}");
+        }
+        assert!(evaluator.matches(&prop("Due").date().is_not_empty(), &filled));
+        // a page without the property does not match either check
+        let missing = page(3, json!({}));
+        assert!(!evaluator.matches(&prop("Due").date().is_empty(), &missing));
+        assert!(!evaluator.matches(&prop("Due").date().is_not_empty(), &missing));
+    }
+
+    #[test]
+    fn date_ranges_match_when_any_day_does() {
+        let evaluator = LocalEvaluator::new();
+        let page = page(1, json!({"Due": date("2026-10-05", Some("2026-10-15"))}));
+        let due = || prop("Due").date();
+        assert!(evaluator.matches(&due().before(day("2026-10-10")), &page));
+        assert!(!evaluator.matches(&due().before(day("2026-10-05")), &page));
+        assert!(evaluator.matches(&due().after(day("2026-10-10")), &page));
+        assert!(!evaluator.matches(&due().after(day("2026-10-15")), &page));
+        assert!(evaluator.matches(&due().equals(day("2026-10-12")), &page));
+    }
+
+    #[test]
+    fn relative_windows_use_now() {
+        let evaluator = LocalEvaluator::at("2026-10-18T12:00:00Z".parse().unwrap());
+        let due = || prop("Due").date();
+        let last_week = page(1, json!({"Due": date("2026-10-13", None)}));
+        let next_week = page(2, json!({"Due": date("2026-10-24", None)}));
+        let last_month = page(3, json!({"Due": date("2026-09-20", None)}));
+        assert!(evaluator.matches(&due().past_week(), &last_week));
+        assert!(!evaluator.matches(&due().next_week(), &last_week));
+        assert!(evaluator.matches(&due().next_week(), &next_week));
+        assert!(!evaluator.matches(&due().past_month(), &next_week));
+        assert!(evaluator.matches(&due().past_month(), &last_month));
+        assert!(!evaluator.matches(&due().past_week(), &last_month));
+    }
+
+    #[test]
+    fn empty_values_sort_last_in_both_directions() {
+        let evaluator = LocalEvaluator::new();
+        let database = database(json!({
+            "Estimate": {"id": "c", "name": "Estimate", "type": "number", "number": {"format": "number"}},
+        }));
+        let estimate = |n, number: Option<u32>| {
+            page(
+                n,
+                json!({"Estimate": {"id": "c", "type": "number", "number": number}}),
+            )
+        };
+        let pages = vec![
+            estimate(1, Some(2)),
+            estimate(2, None),
+            estimate(3, Some(1)),
+        ];
+        let mut ascending = pages.clone();
+        evaluator.sort(&database, &[prop("Estimate").ascending()], &mut ascending);
+        assert_eq!(
+            ids(&ascending),
+            vec![&pages[2].id, &pages[0].id, &pages[1].id]
+        );
+        let mut descending = pages.clone();
+        evaluator.sort(&database, &[prop("Estimate").descending()], &mut descending);
+        assert_eq!(
+            ids(&descending),
+            vec![&pages[0].id, &pages[2].id, &pages[1].id]
+        );
+    }
+
+    #[test]
+    fn options_sort_by_schema_order() {
+        let evaluator = LocalEvaluator::new();
+        let option = |id: &str, name: &str| json!({"id": id, "name": name, "color": "default"});
+        let group = |id: &str, name: &str, options: &[&str]| json!({"id": id, "name": name, "color": "default", "option_ids": options});
+        let database = database(json!({
+            "Priority": {"id": "d", "name": "Priority", "type": "select", "select": {"options": [
+                option("1", "Low"), option("2", "Medium"), option("3", "High"),
+            ]}},
+            "State": {"id": "e", "name": "State", "type": "status", "status": {
+                "options": [option("4", "Done"), option("5", "Not started"), option("6", "In progress")],
+                "groups": [
+                    group("7", "To-do", &["5"]),
+                    group("8", "In progress", &["6"]),
+                    group("9", "Complete", &["4"]),
+                ],
+            }},
+        }));
+        let selected = |n, priority: &str, state: &str| {
+            page(
+                n,
+                json!({
+                    "Priority": {"id": "d", "type": "select", "select": {"name": priority, "color": "default"}},
+                    "State": {"id": "e", "type": "status", "status": {"name": state, "color": "default"}},
+                }),
+            )
+        };
+        let pages = vec![
+            selected(1, "High", "In progress"),
+            selected(2, "Low", "Done"),
+            selected(3, "Medium", "Not started"),
+        ];
+        let mut by_priority = pages.clone();
+        evaluator.sort(&database, &[prop("Priority").ascending()], &mut by_priority);
+        assert_eq!(
+            ids(&by_priority),
+            vec![&pages[1].id, &pages[2].id, &pages[0].id]
+        );
+        let mut by_state = pages.clone();
+        evaluator.sort(&database, &[prop("State").ascending()], &mut by_state);
+        assert_eq!(
+            ids(&by_state),
+            vec![&pages[2].id, &pages[0].id, &pages[1].id]
+        );
+    }
+}
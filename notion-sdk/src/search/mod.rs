@@ -2,6 +2,9 @@
 //! # Notion Search
 //!
 mod api;
+pub mod dsl;
+pub mod local;
+pub mod syntax;
 
 use crate::pages::id::PageId;
 use crate::pagination::{Pageable, Paging, PagingCursor};
@@ -50,7 +53,7 @@ pub struct Filter {
     value: FilterValue,
 }
 
-#[derive(Serialize, Debug, Eq, PartialEq, Default)]
+#[derive(Serialize, Debug, Eq, PartialEq, Default, Clone)]
 pub struct SearchRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     query: Option<String>,
@@ -59,7 +62,19 @@ pub struct SearchRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     filter: Option<Filter>,
     #[serde(flatten)]
-    paging: Option<Paging>,
+    pub paging: Option<Paging>,
+}
+
+impl Pageable for SearchRequest {
+    fn start_from(self, starting_point: Option<PagingCursor>) -> Self {
+        SearchRequest {
+            paging: Some(Paging {
+                start_cursor: starting_point,
+                page_size: self.paging.as_ref().and_then(|p| p.page_size),
+            }),
+            ..self
+        }
+    }
 }
 
 #[derive(Serialize, Debug, Eq, PartialEq, Clone)]
@@ -250,19 +265,55 @@ pub enum FormulaCondition {
     Date(DateCondition),
 }
 
+#[derive(Serialize, Debug, Eq, PartialEq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum RollupCondition {
+    /// Only return pages where any value of the rolled up array matches the provided condition.
+    Any(Box<PropertyCondition>),
+    /// Only return pages where every value of the rolled up array matches the provided condition.
+    Every(Box<PropertyCondition>),
+    /// Only return pages where no value of the rolled up array matches the provided condition.
+    None(Box<PropertyCondition>),
+    /// Only return pages where the rollup computes a number
+    /// and the provided number filter condition matches it.
+    Number(NumberCondition),
+    /// Only return pages where the rollup computes a date
+    /// and the provided date filter condition matches it.
+    Date(DateCondition),
+}
+
 #[derive(Serialize, Debug, Eq, PartialEq, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum PropertyCondition {
+    Title(TextCondition),
     RichText(TextCondition),
+    Url(TextCondition),
+    Email(TextCondition),
+    PhoneNumber(TextCondition),
     Number(NumberCondition),
     Checkbox(CheckboxCondition),
     Select(SelectCondition),
+    /// Status properties are filtered like select properties.
+    Status(SelectCondition),
     MultiSelect(MultiSelectCondition),
     Date(DateCondition),
     People(PeopleCondition),
     Files(FilesCondition),
     Relation(RelationCondition),
     Formula(FormulaCondition),
+    Rollup(RollupCondition),
+    CreatedTime(DateCondition),
+    LastEditedTime(DateCondition),
+    CreatedBy(PeopleCondition),
+    LastEditedBy(PeopleCondition),
+}
+
+/// Filter on when a page was created or last edited, without a property for it in the database.
+#[derive(Serialize, Debug, Eq, PartialEq, Clone)]
+#[serde(tag = "timestamp", rename_all = "snake_case")]
+pub enum TimestampCondition {
+    CreatedTime { created_time: DateCondition },
+    LastEditedTime { last_edited_time: DateCondition },
 }
 
 #[derive(Serialize, Debug, Eq, PartialEq, Clone)]
@@ -273,10 +324,30 @@ pub enum FilterCondition {
         #[serde(flatten)]
         condition: PropertyCondition,
     },
+    Timestamp(TimestampCondition),
     /// Returns pages when **all** of the filters inside the provided vector match.
-    And { and: Vec<FilterCondition> },
+    And {
+        and: Vec<FilterCondition>,
+    },
     /// Returns pages when **any** of the filters inside the provided vector match.
-    Or { or: Vec<FilterCondition> },
+    Or {
+        or: Vec<FilterCondition>,
+    },
+}
+
+/// Levels of `and` and `or` filters Notion accepts in one filter
+pub const MAX_FILTER_LEVELS: usize = 2;
+
+impl FilterCondition {
+    /// levels of `and` and `or` filters, Notion rejects more than [`MAX_FILTER_LEVELS`]
+    pub fn levels(&self) -> usize {
+        match self {
+            FilterCondition::And { and: filters } | FilterCondition::Or { or: filters } => {
+                1 + filters.iter().map(Self::levels).max().unwrap_or(0)
+            }
+            _ => 0,
+        }
+    }
 }
 
 #[derive(Serialize, Debug, Eq, PartialEq, Hash, Copy, Clone)]
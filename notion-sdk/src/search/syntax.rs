@@ -0,0 +1,1290 @@
+//!
+//! # Filter Syntax
+//!
+//! A text form of [`FilterCondition`] for people who type filters instead of writing Rust.
+//! [`parse_filter`] reads it, using the schema of the database to pick the type of every condition,
+//! and [`FilterCondition`] implements [`Display`] to write it back.
+//!
+//! ```text
+//! Status = "Done" AND Due < 2026-11-01 OR Tags contains "infra"
+//! ("Story points" >= 3 OR Estimate is empty) AND @last_edited_time within past_week
+//! ```
+//!
+//! - `AND` binds tighter than `OR`, group with parentheses, keywords ignore case
+//! - property names with spaces or symbols are quoted like text: `"Story points"`
+//! - `@created_time` and `@last_edited_time` filter on the timestamps of the page itself
+//! - operators: `=`, `!=`, `<`, `>`, `<=`, `>=`, `contains`, `not contains`, `starts with`,
+//!   `ends with`, `is empty`, `is not empty` and `within` followed by `past_week`, `past_month`,
+//!   `past_year`, `next_week`, `next_month` or `next_year`
+//! - values: `"text"`, bare words, numbers, `true`/`false`, dates like `2026-11-01`
+//!   or `2026-11-01T09:30:00Z`, ids of users and pages in quotes
+//! - rollups compare what they compute, a date for `earliest_date`, `latest_date` and `date_range`
+//!   and a number for the other functions, or each value they collect after `any`, `every` or `none`
+//!   followed by the type of the values: `Tasks any rich_text contains "infra"`
+//! - Notion accepts compound filters nested two levels deep, like `A AND (B OR C)`,
+//!   deeper groups are rejected
+//!
+//! ## Examples
+//! ```rust,no_run
+//! use notion_sdk::search::syntax::parse_filter;
+//! use notion_sdk::search::DatabaseQuery;
+//! use notion_sdk::NotionApi;
+//! # async fn run() -> Result<(), notion_sdk::error::Error> {
+//! let notion = NotionApi::new("token")?;
+//! let database_id: notion_sdk::database::id::DatabaseId = "3f8d5d7e-6c0a-4d9b-9a51-9f3c2b1e8a10".parse()?;
+//! let database = notion.databases_retrieve(database_id.clone()).await?;
+//! let input = r#"Status = "Done" AND Due < 2026-11-01 OR Tags contains "infra""#;
+//! match parse_filter(&database, input) {
+//!     Ok(filter) => {
+//!         println!("{filter}");
+//!         let query = DatabaseQuery {
+//!             filter: Some(filter),
+//!             ..Default::default()
+//!         };
+//!         let pages = notion.databases_query(database_id, Some(query)).await?;
+//!     }
+//!     Err(error) => eprintln!("{}: {error}", &input[error.span.clone()]),
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use crate::database::properties::PropertyConfiguration;
+use crate::database::rollup::RollupFunction;
+use crate::database::Database;
+use crate::pages::id::PageId;
+use crate::search::{
+    CheckboxCondition, DateCondition, FilesCondition, FilterCondition, FormulaCondition,
+    MultiSelectCondition, NumberCondition, PeopleCondition, PropertyCondition, RelationCondition,
+    RollupCondition, SelectCondition, TextCondition, TimestampCondition, MAX_FILTER_LEVELS,
+};
+use crate::user::id::UserId;
+use chrono::{DateTime, NaiveDate, SecondsFormat, TimeZone, Timelike, Utc};
+use serde_json::Number;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+/// Input [`parse_filter`] could not read.
+#[derive(Debug, Clone, Eq, PartialEq, thiserror::Error)]
+#[error("{message} at {}..{}", .span.start, .span.end)]
+pub struct FilterParseError {
+    pub message: String,
+    /// Byte range of the offending input, empty at the end of the input when more was expected.
+    pub span: Range<usize>,
+}
+
+impl FilterParseError {
+    fn new<T: Into<String>>(message: T, span: Range<usize>) -> Self {
+        FilterParseError {
+            message: message.into(),
+            span,
+        }
+    }
+}
+
+/// Parse a filter, picking the type of every condition from the properties of `database`.
+pub fn parse_filter(database: &Database, input: &str) -> Result<FilterCondition, FilterParseError> {
+    let mut parser = Parser {
+        tokens: tokenize(input)?,
+        position: 0,
+        end: input.len(),
+        depth: 0,
+        database,
+    };
+    let (filter, group) = parser.or()?;
+    if let Some(token) = parser.tokens.get(parser.position) {
+        return Err(FilterParseError::new(
+            "expected AND, OR or the end of the filter",
+            token.span.clone(),
+        ));
+    }
+    check_levels(group.as_slice(), 1)?;
+    Ok(filter)
+}
+
+/// Parentheses deeper than this are rejected instead of overflowing the stack
+const MAX_DEPTH: usize = 32;
+
+/// A compound filter and the compound filters in it, with their place in the input
+#[derive(Debug)]
+struct Group {
+    span: Range<usize>,
+    groups: Vec<Group>,
+}
+
+fn check_levels(groups: &[Group], level: usize) -> Result<(), FilterParseError> {
+    for group in groups {
+        if level > MAX_FILTER_LEVELS {
+            return Err(FilterParseError::new(
+                "AND and OR can only be nested two levels deep",
+                group.span.clone(),
+            ));
+        }
+        check_levels(&group.groups, level + 1)?;
+    }
+    Ok(())
+}
+
+const KEYWORDS: &[&str] = &[
+    "and", "or", "not", "contains", "starts", "ends", "with", "is", "empty", "within", "any",
+    "every", "none", "true", "false",
+];
+
+/// Types of the values a rollup collects, as named by the Notion API
+const ITEM_KINDS: &[&str] = &[
+    "title",
+    "rich_text",
+    "url",
+    "email",
+    "phone_number",
+    "number",
+    "checkbox",
+    "select",
+    "status",
+    "multi_select",
+    "date",
+    "created_time",
+    "last_edited_time",
+    "people",
+    "created_by",
+    "last_edited_by",
+    "files",
+    "relation",
+];
+
+const RELATIVE_DATES: &[(&str, DateCondition)] = &[
+    ("past_week", DateCondition::PastWeek),
+    ("past_month", DateCondition::PastMonth),
+    ("past_year", DateCondition::PastYear),
+    ("next_week", DateCondition::NextWeek),
+    ("next_month", DateCondition::NextMonth),
+    ("next_year", DateCondition::NextYear),
+];
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Token {
+    /// property names, keywords and bare values
+    Word(String),
+    /// `@created_time`, without the `@`
+    Timestamp(String),
+    Text(String),
+    /// numbers and dates, checked once the expected type is known
+    Literal(String),
+    Operator(&'static str),
+    Open,
+    Close,
+}
+
+#[derive(Debug, Clone)]
+struct Spanned {
+    token: Token,
+    span: Range<usize>,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Spanned>, FilterParseError> {
+    let mut tokens = vec![];
+    let mut chars = input.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        let token = match c {
+            c if c.is_whitespace() => {
+                chars.next();
+                continue;
+            }
+            '(' | ')' => {
+                chars.next();
+                if c == '(' {
+                    Token::Open
+                } else {
+                    Token::Close
+                }
+            }
+            '"' => {
+                chars.next();
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, c)) => text.push(c),
+                            None => {
+                                return Err(FilterParseError::new(
+                                    "unterminated text",
+                                    start..input.len(),
+                                ))
+                            }
+                        },
+                        Some((_, c)) => text.push(c),
+                        None => {
+                            return Err(FilterParseError::new(
+                                "unterminated text",
+                                start..input.len(),
+                            ))
+                        }
+                    }
+                }
+                Token::Text(text)
+            }
+            '=' | '!' | '<' | '>' => {
+                chars.next();
+                let equals = chars.next_if(|&(_, c)| c == '=').is_some();
+                Token::Operator(match (c, equals) {
+                    ('=', false) => "=",
+                    ('!', true) => "!=",
+                    ('<', false) => "<",
+                    ('<', true) => "<=",
+                    ('>', false) => ">",
+                    ('>', true) => ">=",
+                    _ => {
+                        let end = chars.peek().map_or(input.len(), |&(i, _)| i);
+                        return Err(FilterParseError::new("unknown operator", start..end));
+                    }
+                })
+            }
+            '@' => {
+                chars.next();
+                Token::Timestamp(take_while(input, &mut chars, is_word_char).to_string())
+            }
+            c if c.is_ascii_digit() || c == '-' => Token::Literal(
+                take_while(input, &mut chars, |c| {
+                    c.is_ascii_alphanumeric() || matches!(c, ':' | '.' | '+' | '-')
+                })
+                .to_string(),
+            ),
+            c if is_word_char(c) => {
+                Token::Word(take_while(input, &mut chars, is_word_char).to_string())
+            }
+            c => {
+                return Err(FilterParseError::new(
+                    format!("unexpected character {c:?}"),
+                    start..start + c.len_utf8(),
+                ))
+            }
+        };
+        let end = chars.peek().map_or(input.len(), |&(i, _)| i);
+        tokens.push(Spanned {
+            token,
+            span: start..end,
+        });
+    }
+    Ok(tokens)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn take_while<'a>(
+    input: &'a str,
+    chars: &mut std::iter::Peekable<std::str::CharIndices>,
+    accept: impl Fn(char) -> bool,
+) -> &'a str {
+    let start = chars.peek().map_or(input.len(), |&(i, _)| i);
+    while chars.next_if(|&(_, c)| accept(c)).is_some() {}
+    let end = chars.peek().map_or(input.len(), |&(i, _)| i);
+    &input[start..end]
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Operator {
+    Equals,
+    DoesNotEqual,
+    Less,
+    Greater,
+    LessOrEqual,
+    GreaterOrEqual,
+    Contains,
+    DoesNotContain,
+    StartsWith,
+    EndsWith,
+    IsEmpty,
+    IsNotEmpty,
+    Within(DateCondition),
+}
+
+impl Operator {
+    fn takes_value(&self) -> bool {
+        !matches!(
+            self,
+            Operator::IsEmpty | Operator::IsNotEmpty | Operator::Within(_)
+        )
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Value {
+    Text(String),
+    /// number or date
+    Literal(String),
+    Boolean(bool),
+}
+
+/// The operator and value following a property, converted once the type of the property is known
+struct Comparison {
+    operator: Operator,
+    operator_span: Range<usize>,
+    value: Option<(Value, Range<usize>)>,
+}
+
+/// What a value looks like, for formulas and rollups whose result type is not in the schema
+enum Inferred {
+    Text,
+    Number,
+    Checkbox,
+    Date,
+}
+
+impl Comparison {
+    fn unsupported(&self, kind: &str) -> FilterParseError {
+        FilterParseError::new(
+            format!("this operator can not be used on {kind} properties"),
+            self.operator_span.clone(),
+        )
+    }
+    fn value(&self) -> (&Value, Range<usize>) {
+        match &self.value {
+            Some((value, span)) => (value, span.clone()),
+            // operators taking a value were parsed with one
+            None => unreachable!("operator without a value"),
+        }
+    }
+    fn text(&self) -> Result<String, FilterParseError> {
+        match self.value() {
+            (Value::Text(text) | Value::Literal(text), _) => Ok(text.clone()),
+            (_, span) => Err(FilterParseError::new("expected text", span)),
+        }
+    }
+    fn number(&self) -> Result<Number, FilterParseError> {
+        match self.value() {
+            (Value::Literal(literal), span) => literal
+                .parse()
+                .map_err(|_| FilterParseError::new("expected a number", span)),
+            (_, span) => Err(FilterParseError::new("expected a number", span)),
+        }
+    }
+    fn boolean(&self) -> Result<bool, FilterParseError> {
+        match self.value() {
+            (Value::Boolean(boolean), _) => Ok(*boolean),
+            (_, span) => Err(FilterParseError::new("expected true or false", span)),
+        }
+    }
+    fn date(&self) -> Result<DateTime<Utc>, FilterParseError> {
+        match self.value() {
+            (Value::Literal(literal) | Value::Text(literal), span) => parse_date(literal)
+                .ok_or_else(|| FilterParseError::new("expected a date like 2026-11-01", span)),
+            (_, span) => Err(FilterParseError::new(
+                "expected a date like 2026-11-01",
+                span,
+            )),
+        }
+    }
+    fn user(&self) -> Result<UserId, FilterParseError> {
+        let (_, span) = self.value();
+        self.text()?
+            .parse()
+            .map_err(|_| FilterParseError::new("expected the id of a user", span))
+    }
+    fn page(&self) -> Result<PageId, FilterParseError> {
+        let (_, span) = self.value();
+        self.text()?
+            .parse()
+            .map_err(|_| FilterParseError::new("expected the id of a page", span))
+    }
+    fn inferred(&self) -> Inferred {
+        match (&self.operator, &self.value) {
+            (Operator::Within(_), _) => Inferred::Date,
+            (_, Some((Value::Boolean(_), _))) => Inferred::Checkbox,
+            (_, Some((Value::Literal(literal), _))) if literal.parse::<Number>().is_ok() => {
+                Inferred::Number
+            }
+            (_, Some((Value::Literal(literal), _))) if parse_date(literal).is_some() => {
+                Inferred::Date
+            }
+            _ => Inferred::Text,
+        }
+    }
+
+    fn text_condition(&self, kind: &str) -> Result<TextCondition, FilterParseError> {
+        Ok(match self.operator {
+            Operator::Equals => TextCondition::Equals(self.text()?),
+            Operator::DoesNotEqual => TextCondition::DoesNotEqual(self.text()?),
+            Operator::Contains => TextCondition::Contains(self.text()?),
+            Operator::DoesNotContain => TextCondition::DoesNotContain(self.text()?),
+            Operator::StartsWith => TextCondition::StartsWith(self.text()?),
+            Operator::EndsWith => TextCondition::EndsWith(self.text()?),
+            Operator::IsEmpty => TextCondition::IsEmpty,
+            Operator::IsNotEmpty => TextCondition::IsNotEmpty,
+            _ => return Err(self.unsupported(kind)),
+        })
+    }
+    fn number_condition(&self, kind: &str) -> Result<NumberCondition, FilterParseError> {
+        Ok(match self.operator {
+            Operator::Equals => NumberCondition::Equals(self.number()?),
+            Operator::DoesNotEqual => NumberCondition::DoesNotEqual(self.number()?),
+            Operator::Greater => NumberCondition::GreaterThan(self.number()?),
+            Operator::Less => NumberCondition::LessThan(self.number()?),
+            Operator::GreaterOrEqual => NumberCondition::GreaterThanOrEqualTo(self.number()?),
+            Operator::LessOrEqual => NumberCondition::LessThanOrEqualTo(self.number()?),
+            Operator::IsEmpty => NumberCondition::IsEmpty,
+            Operator::IsNotEmpty => NumberCondition::IsNotEmpty,
+            _ => return Err(self.unsupported(kind)),
+        })
+    }
+    fn checkbox_condition(&self, kind: &str) -> Result<CheckboxCondition, FilterParseError> {
+        Ok(match self.operator {
+            Operator::Equals => CheckboxCondition::Equals(self.boolean()?),
+            Operator::DoesNotEqual => CheckboxCondition::DoesNotEqual(self.boolean()?),
+            _ => return Err(self.unsupported(kind)),
+        })
+    }
+    fn select_condition(&self, kind: &str) -> Result<SelectCondition, FilterParseError> {
+        Ok(match self.operator {
+            Operator::Equals => SelectCondition::Equals(self.text()?),
+            Operator::DoesNotEqual => SelectCondition::DoesNotEqual(self.text()?),
+            Operator::IsEmpty => SelectCondition::IsEmpty,
+            Operator::IsNotEmpty => SelectCondition::IsNotEmpty,
+            _ => return Err(self.unsupported(kind)),
+        })
+    }
+    fn multi_select_condition(&self, kind: &str) -> Result<MultiSelectCondition, FilterParseError> {
+        Ok(match self.operator {
+            Operator::Contains => MultiSelectCondition::Contains(self.text()?),
+            Operator::DoesNotContain => MultiSelectCondition::DoesNotContain(self.text()?),
+            Operator::IsEmpty => MultiSelectCondition::IsEmpty,
+            Operator::IsNotEmpty => MultiSelectCondition::IsNotEmpty,
+            _ => return Err(self.unsupported(kind)),
+        })
+    }
+    fn date_condition(&self, kind: &str) -> Result<DateCondition, FilterParseError> {
+        Ok(match &self.operator {
+            Operator::Equals => DateCondition::Equals(self.date()?),
+            Operator::Less => DateCondition::Before(self.date()?),
+            Operator::Greater => DateCondition::After(self.date()?),
+            Operator::LessOrEqual => DateCondition::OnOrBefore(self.date()?),
+            Operator::GreaterOrEqual => DateCondition::OnOrAfter(self.date()?),
+            Operator::IsEmpty => DateCondition::IsEmpty,
+            Operator::IsNotEmpty => DateCondition::IsNotEmpty,
+            Operator::Within(relative) => relative.clone(),
+            _ => return Err(self.unsupported(kind)),
+        })
+    }
+    fn people_condition(&self, kind: &str) -> Result<PeopleCondition, FilterParseError> {
+        Ok(match self.operator {
+            Operator::Contains => PeopleCondition::Contains(self.user()?),
+            Operator::DoesNotContain => PeopleCondition::DoesNotContain(self.user()?),
+            Operator::IsEmpty => PeopleCondition::IsEmpty,
+            Operator::IsNotEmpty => PeopleCondition::IsNotEmpty,
+            _ => return Err(self.unsupported(kind)),
+        })
+    }
+    fn files_condition(&self, kind: &str) -> Result<FilesCondition, FilterParseError> {
+        Ok(match self.operator {
+            Operator::IsEmpty => FilesCondition::IsEmpty,
+            Operator::IsNotEmpty => FilesCondition::IsNotEmpty,
+            _ => return Err(self.unsupported(kind)),
+        })
+    }
+    fn relation_condition(&self, kind: &str) -> Result<RelationCondition, FilterParseError> {
+        Ok(match self.operator {
+            Operator::Contains => RelationCondition::Contains(self.page()?),
+            Operator::DoesNotContain => RelationCondition::DoesNotContain(self.page()?),
+            Operator::IsEmpty => RelationCondition::IsEmpty,
+            Operator::IsNotEmpty => RelationCondition::IsNotEmpty,
+            _ => return Err(self.unsupported(kind)),
+        })
+    }
+    /// condition on a property of type `kind`, one of [`ITEM_KINDS`]
+    fn condition(&self, kind: &str) -> Result<PropertyCondition, FilterParseError> {
+        Ok(match kind {
+            "title" => PropertyCondition::Title(self.text_condition(kind)?),
+            "rich_text" => PropertyCondition::RichText(self.text_condition(kind)?),
+            "url" => PropertyCondition::Url(self.text_condition(kind)?),
+            "email" => PropertyCondition::Email(self.text_condition(kind)?),
+            "phone_number" => PropertyCondition::PhoneNumber(self.text_condition(kind)?),
+            "number" => PropertyCondition::Number(self.number_condition(kind)?),
+            "checkbox" => PropertyCondition::Checkbox(self.checkbox_condition(kind)?),
+            "select" => PropertyCondition::Select(self.select_condition(kind)?),
+            "status" => PropertyCondition::Status(self.select_condition(kind)?),
+            "multi_select" => PropertyCondition::MultiSelect(self.multi_select_condition(kind)?),
+            "date" => PropertyCondition::Date(self.date_condition(kind)?),
+            "created_time" => PropertyCondition::CreatedTime(self.date_condition(kind)?),
+            "last_edited_time" => PropertyCondition::LastEditedTime(self.date_condition(kind)?),
+            "people" => PropertyCondition::People(self.people_condition(kind)?),
+            "created_by" => PropertyCondition::CreatedBy(self.people_condition(kind)?),
+            "last_edited_by" => PropertyCondition::LastEditedBy(self.people_condition(kind)?),
+            "files" => PropertyCondition::Files(self.files_condition(kind)?),
+            "relation" => PropertyCondition::Relation(self.relation_condition(kind)?),
+            _ => unreachable!("{kind} is not one of the item kinds"),
+        })
+    }
+    /// condition on a value whose type is only known from the value itself
+    fn inferred_condition(&self) -> Result<PropertyCondition, FilterParseError> {
+        Ok(match self.inferred() {
+            Inferred::Text => PropertyCondition::RichText(self.text_condition("text")?),
+            Inferred::Number => PropertyCondition::Number(self.number_condition("number")?),
+            Inferred::Checkbox => PropertyCondition::Checkbox(self.checkbox_condition("checkbox")?),
+            Inferred::Date => PropertyCondition::Date(self.date_condition("date")?),
+        })
+    }
+}
+
+fn parse_date(literal: &str) -> Option<DateTime<Utc>> {
+    if let Ok(date) = NaiveDate::parse_from_str(literal, "%Y-%m-%d") {
+        return date
+            .and_hms_opt(0, 0, 0)
+            .map(|midnight| Utc.from_utc_datetime(&midnight));
+    }
+    DateTime::parse_from_rfc3339(literal)
+        .ok()
+        .map(|time| time.with_timezone(&Utc))
+}
+
+struct Parser<'a> {
+    tokens: Vec<Spanned>,
+    position: usize,
+    end: usize,
+    /// parentheses around the next token
+    depth: usize,
+    database: &'a Database,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Spanned> {
+        self.tokens.get(self.position)
+    }
+    fn next(&mut self) -> Option<Spanned> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+    /// span of the next token, or the end of the input
+    fn next_span(&self) -> Range<usize> {
+        self.peek()
+            .map_or(self.end..self.end, |token| token.span.clone())
+    }
+    /// end of the last token read
+    fn previous_end(&self) -> usize {
+        self.position
+            .checked_sub(1)
+            .and_then(|position| self.tokens.get(position))
+            .map_or(self.end, |token| token.span.end)
+    }
+    fn at_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Spanned { token: Token::Word(word), .. }) if word.eq_ignore_ascii_case(keyword))
+    }
+    fn keyword(&mut self, keyword: &str) -> Result<Range<usize>, FilterParseError> {
+        if self.at_keyword(keyword) {
+            Ok(self.next().map(|token| token.span).unwrap_or_default())
+        } else {
+            Err(FilterParseError::new(
+                format!("expected `{keyword}`"),
+                self.next_span(),
+            ))
+        }
+    }
+
+    fn or(&mut self) -> Result<(FilterCondition, Option<Group>), FilterParseError> {
+        let start = self.next_span().start;
+        let (filter, group) = self.and()?;
+        let mut or = vec![filter];
+        let mut groups: Vec<Group> = group.into_iter().collect();
+        while self.at_keyword("or") {
+            self.next();
+            let (filter, group) = self.and()?;
+            or.push(filter);
+            groups.extend(group);
+        }
+        Ok(match or.len() {
+            1 => (or.remove(0), groups.pop()),
+            _ => (
+                FilterCondition::Or { or },
+                Some(Group {
+                    span: start..self.previous_end(),
+                    groups,
+                }),
+            ),
+        })
+    }
+
+    fn and(&mut self) -> Result<(FilterCondition, Option<Group>), FilterParseError> {
+        let start = self.next_span().start;
+        let (filter, group) = self.term()?;
+        let mut and = vec![filter];
+        let mut groups: Vec<Group> = group.into_iter().collect();
+        while self.at_keyword("and") {
+            self.next();
+            let (filter, group) = self.term()?;
+            and.push(filter);
+            groups.extend(group);
+        }
+        Ok(match and.len() {
+            1 => (and.remove(0), groups.pop()),
+            _ => (
+                FilterCondition::And { and },
+                Some(Group {
+                    span: start..self.previous_end(),
+                    groups,
+                }),
+            ),
+        })
+    }
+
+    /// a condition, or a group in parentheses with the compound filter it holds
+    fn term(&mut self) -> Result<(FilterCondition, Option<Group>), FilterParseError> {
+        let Some(Spanned { token, span }) = self.next() else {
+            return Err(FilterParseError::new(
+                "expected a property name or `(`",
+                self.end..self.end,
+            ));
+        };
+        match token {
+            Token::Open => {
+                if self.depth == MAX_DEPTH {
+                    return Err(FilterParseError::new(
+                        format!("parentheses can only be nested {MAX_DEPTH} deep"),
+                        span,
+                    ));
+                }
+                self.depth += 1;
+                let (filter, group) = self.or()?;
+                self.depth -= 1;
+                match self.next() {
+                    Some(Spanned {
+                        token: Token::Close,
+                        span: close,
+                    }) => Ok((
+                        filter,
+                        group.map(|group| Group {
+                            span: span.start..close.end,
+                            ..group
+                        }),
+                    )),
+                    _ => Err(FilterParseError::new(
+                        "expected `)`",
+                        self.tokens
+                            .get(self.position - 1)
+                            .map_or(self.end..self.end, |token| token.span.clone()),
+                    )),
+                }
+            }
+            Token::Timestamp(timestamp) => {
+                let created = match timestamp.as_str() {
+                    "created_time" => true,
+                    "last_edited_time" => false,
+                    _ => {
+                        return Err(FilterParseError::new(
+                            "expected @created_time or @last_edited_time",
+                            span,
+                        ))
+                    }
+                };
+                let condition = self.comparison()?.date_condition("timestamp")?;
+                let timestamp = if created {
+                    TimestampCondition::CreatedTime {
+                        created_time: condition,
+                    }
+                } else {
+                    TimestampCondition::LastEditedTime {
+                        last_edited_time: condition,
+                    }
+                };
+                Ok((FilterCondition::Timestamp(timestamp), None))
+            }
+            Token::Word(name) | Token::Text(name) => {
+                let Some(property) = self.database.find_property(&name) else {
+                    return Err(FilterParseError::new(
+                        format!("no property named {name:?}"),
+                        span,
+                    ));
+                };
+                let condition = self.property(property)?;
+                let filter = FilterCondition::Property {
+                    property: name,
+                    condition,
+                };
+                Ok((filter, None))
+            }
+            _ => Err(FilterParseError::new(
+                "expected a property name or `(`",
+                span,
+            )),
+        }
+    }
+
+    fn property(
+        &mut self,
+        property: &PropertyConfiguration,
+    ) -> Result<PropertyCondition, FilterParseError> {
+        let kind = property.type_name();
+        if let PropertyConfiguration::Rollup { rollup, .. } = property {
+            let quantifier = [
+                ("any", RollupCondition::Any as fn(_) -> _),
+                ("every", RollupCondition::Every),
+                ("none", RollupCondition::None),
+            ]
+            .into_iter()
+            .find(|(keyword, _)| self.at_keyword(keyword));
+            if let Some((_, quantifier)) = quantifier {
+                self.next();
+                let item_span = self.next_span();
+                let item_kind = match self.next() {
+                    Some(Spanned {
+                        token: Token::Word(word),
+                        ..
+                    }) => ITEM_KINDS
+                        .iter()
+                        .find(|kind| word.eq_ignore_ascii_case(kind)),
+                    _ => None,
+                };
+                let Some(item_kind) = item_kind else {
+                    return Err(FilterParseError::new(
+                        "expected the type of the values the rollup collects, like `any number > 3`",
+                        item_span,
+                    ));
+                };
+                let condition = self.comparison()?.condition(item_kind)?;
+                return Ok(PropertyCondition::Rollup(quantifier(Box::new(condition))));
+            }
+            let comparison = self.comparison()?;
+            return Ok(PropertyCondition::Rollup(match rollup.function {
+                RollupFunction::EarliestDate
+                | RollupFunction::LatestDate
+                | RollupFunction::DateRange => {
+                    RollupCondition::Date(comparison.date_condition(kind)?)
+                }
+                RollupFunction::ShowOriginal | RollupFunction::ShowUnique => {
+                    return Err(FilterParseError::new(
+                        "this rollup collects values, compare them with any, every or none",
+                        comparison.operator_span,
+                    ))
+                }
+                _ => RollupCondition::Number(comparison.number_condition(kind)?),
+            }));
+        }
+        let comparison = self.comparison()?;
+        Ok(match property {
+            PropertyConfiguration::Formula { .. } => {
+                PropertyCondition::Formula(match comparison.inferred_condition()? {
+                    PropertyCondition::Number(condition) => FormulaCondition::Number(condition),
+                    PropertyCondition::Checkbox(condition) => FormulaCondition::Checkbox(condition),
+                    PropertyCondition::Date(condition) => FormulaCondition::Date(condition),
+                    PropertyCondition::RichText(condition) => FormulaCondition::Text(condition),
+                    _ => unreachable!("inferred conditions are text, number, checkbox or date"),
+                })
+            }
+            _ => comparison.condition(kind)?,
+        })
+    }
+
+    fn comparison(&mut self) -> Result<Comparison, FilterParseError> {
+        let (operator, operator_span) = self.operator()?;
+        let value = if operator.takes_value() {
+            Some(self.value()?)
+        } else {
+            None
+        };
+        Ok(Comparison {
+            operator,
+            operator_span,
+            value,
+        })
+    }
+
+    fn operator(&mut self) -> Result<(Operator, Range<usize>), FilterParseError> {
+        let span = self.next_span();
+        let Some(Spanned { token, .. }) = self.next() else {
+            return Err(FilterParseError::new("expected an operator", span));
+        };
+        let operator = match token {
+            Token::Operator(operator) => match operator {
+                "=" => Operator::Equals,
+                "!=" => Operator::DoesNotEqual,
+                "<" => Operator::Less,
+                ">" => Operator::Greater,
+                "<=" => Operator::LessOrEqual,
+                _ => Operator::GreaterOrEqual,
+            },
+            Token::Word(word) => match word.to_ascii_lowercase().as_str() {
+                "contains" => Operator::Contains,
+                "not" => {
+                    let end = self.keyword("contains")?;
+                    return Ok((Operator::DoesNotContain, span.start..end.end));
+                }
+                "starts" => {
+                    let end = self.keyword("with")?;
+                    return Ok((Operator::StartsWith, span.start..end.end));
+                }
+                "ends" => {
+                    let end = self.keyword("with")?;
+                    return Ok((Operator::EndsWith, span.start..end.end));
+                }
+                "is" => {
+                    let negated = self.at_keyword("not");
+                    if negated {
+                        self.next();
+                    }
+                    let end = self.keyword("empty")?;
+                    let operator = if negated {
+                        Operator::IsNotEmpty
+                    } else {
+                        Operator::IsEmpty
+                    };
+                    return Ok((operator, span.start..end.end));
+                }
+                "within" => {
+                    let relative = self.next_span();
+                    let condition = match self.next() {
+                        Some(Spanned {
+                            token: Token::Word(word),
+                            ..
+                        }) => RELATIVE_DATES
+                            .iter()
+                            .find(|(name, _)| word.eq_ignore_ascii_case(name))
+                            .map(|(_, condition)| condition.clone()),
+                        _ => None,
+                    };
+                    return match condition {
+                        Some(condition) => {
+                            Ok((Operator::Within(condition), span.start..relative.end))
+                        }
+                        None => Err(FilterParseError::new(
+                            "expected past_week, past_month, past_year, next_week, next_month or next_year",
+                            relative,
+                        )),
+                    };
+                }
+                _ => return Err(FilterParseError::new("expected an operator", span)),
+            },
+            _ => return Err(FilterParseError::new("expected an operator", span)),
+        };
+        Ok((operator, span))
+    }
+
+    fn value(&mut self) -> Result<(Value, Range<usize>), FilterParseError> {
+        let span = self.next_span();
+        let value = match self.next().map(|token| token.token) {
+            Some(Token::Text(text)) => Value::Text(text),
+            Some(Token::Literal(literal)) => Value::Literal(literal),
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case("true") => Value::Boolean(true),
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case("false") => Value::Boolean(false),
+            Some(Token::Word(word))
+                if !matches!(word.to_ascii_lowercase().as_str(), "and" | "or") =>
+            {
+                Value::Text(word)
+            }
+            _ => return Err(FilterParseError::new("expected a value", span)),
+        };
+        Ok((value, span))
+    }
+}
+
+/// Writes the filter in the syntax [`parse_filter`] reads.
+impl Display for FilterCondition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FilterCondition::Property {
+                property,
+                condition,
+            } => {
+                write_name(f, property)?;
+                write_condition(f, condition)
+            }
+            FilterCondition::Timestamp(TimestampCondition::CreatedTime { created_time }) => {
+                f.write_str("@created_time")?;
+                write_date(f, created_time)
+            }
+            FilterCondition::Timestamp(TimestampCondition::LastEditedTime { last_edited_time }) => {
+                f.write_str("@last_edited_time")?;
+                write_date(f, last_edited_time)
+            }
+            FilterCondition::And { and } => {
+                for (index, filter) in and.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(" AND ")?;
+                    }
+                    match filter {
+                        FilterCondition::And { .. } | FilterCondition::Or { .. } => {
+                            write!(f, "({filter})")?
+                        }
+                        filter => write!(f, "{filter}")?,
+                    }
+                }
+                Ok(())
+            }
+            FilterCondition::Or { or } => {
+                for (index, filter) in or.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(" OR ")?;
+                    }
+                    match filter {
+                        FilterCondition::Or { .. } => write!(f, "({filter})")?,
+                        filter => write!(f, "{filter}")?,
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn write_name(f: &mut Formatter<'_>, name: &str) -> std::fmt::Result {
+    let bare = name.chars().next().is_some_and(|c| !c.is_ascii_digit())
+        && name.chars().all(is_word_char)
+        && !KEYWORDS
+            .iter()
+            .any(|keyword| name.eq_ignore_ascii_case(keyword));
+    if bare {
+        f.write_str(name)
+    } else {
+        write_text(f, name)
+    }
+}
+
+fn write_text(f: &mut Formatter<'_>, text: &str) -> std::fmt::Result {
+    write!(f, "\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn write_condition(f: &mut Formatter<'_>, condition: &PropertyCondition) -> std::fmt::Result {
+    match condition {
+        PropertyCondition::Title(condition)
+        | PropertyCondition::RichText(condition)
+        | PropertyCondition::Url(condition)
+        | PropertyCondition::Email(condition)
+        | PropertyCondition::PhoneNumber(condition) => write_text_condition(f, condition),
+        PropertyCondition::Number(condition) => write_number(f, condition),
+        PropertyCondition::Checkbox(condition) => write_checkbox(f, condition),
+        PropertyCondition::Select(condition) | PropertyCondition::Status(condition) => {
+            match condition {
+                SelectCondition::Equals(value) => {
+                    f.write_str(" = ")?;
+                    write_text(f, value)
+                }
+                SelectCondition::DoesNotEqual(value) => {
+                    f.write_str(" != ")?;
+                    write_text(f, value)
+                }
+                SelectCondition::IsEmpty => f.write_str(" is empty"),
+                SelectCondition::IsNotEmpty => f.write_str(" is not empty"),
+            }
+        }
+        PropertyCondition::MultiSelect(condition) => match condition {
+            MultiSelectCondition::Contains(value) => {
+                f.write_str(" contains ")?;
+                write_text(f, value)
+            }
+            MultiSelectCondition::DoesNotContain(value) => {
+                f.write_str(" not contains ")?;
+                write_text(f, value)
+            }
+            MultiSelectCondition::IsEmpty => f.write_str(" is empty"),
+            MultiSelectCondition::IsNotEmpty => f.write_str(" is not empty"),
+        },
+        PropertyCondition::Date(condition)
+        | PropertyCondition::CreatedTime(condition)
+        | PropertyCondition::LastEditedTime(condition) => write_date(f, condition),
+        PropertyCondition::People(condition)
+        | PropertyCondition::CreatedBy(condition)
+        | PropertyCondition::LastEditedBy(condition) => match condition {
+            PeopleCondition::Contains(user) => write!(f, " contains \"{user}\""),
+            PeopleCondition::DoesNotContain(user) => write!(f, " not contains \"{user}\""),
+            PeopleCondition::IsEmpty => f.write_str(" is empty"),
+            PeopleCondition::IsNotEmpty => f.write_str(" is not empty"),
+        },
+        PropertyCondition::Files(condition) => match condition {
+            FilesCondition::IsEmpty => f.write_str(" is empty"),
+            FilesCondition::IsNotEmpty => f.write_str(" is not empty"),
+        },
+        PropertyCondition::Relation(condition) => match condition {
+            RelationCondition::Contains(page) => write!(f, " contains \"{page}\""),
+            RelationCondition::DoesNotContain(page) => write!(f, " not contains \"{page}\""),
+            RelationCondition::IsEmpty => f.write_str(" is empty"),
+            RelationCondition::IsNotEmpty => f.write_str(" is not empty"),
+        },
+        PropertyCondition::Formula(condition) => match condition {
+            FormulaCondition::Text(condition) => write_text_condition(f, condition),
+            FormulaCondition::Number(condition) => write_number(f, condition),
+            FormulaCondition::Checkbox(condition) => write_checkbox(f, condition),
+            FormulaCondition::Date(condition) => write_date(f, condition),
+        },
+        PropertyCondition::Rollup(condition) => match condition {
+            RollupCondition::Any(condition) => {
+                write!(f, " any {}", condition_kind(condition))?;
+                write_condition(f, condition)
+            }
+            RollupCondition::Every(condition) => {
+                write!(f, " every {}", condition_kind(condition))?;
+                write_condition(f, condition)
+            }
+            RollupCondition::None(condition) => {
+                write!(f, " none {}", condition_kind(condition))?;
+                write_condition(f, condition)
+            }
+            // the rollup function picks between numbers and dates when parsing
+            RollupCondition::Number(condition) => write_number(f, condition),
+            RollupCondition::Date(condition) => write_date(f, condition),
+        },
+    }
+}
+
+/// type of the property a condition is for, as named by the Notion API
+fn condition_kind(condition: &PropertyCondition) -> &'static str {
+    match condition {
+        PropertyCondition::Title(_) => "title",
+        PropertyCondition::RichText(_) => "rich_text",
+        PropertyCondition::Url(_) => "url",
+        PropertyCondition::Email(_) => "email",
+        PropertyCondition::PhoneNumber(_) => "phone_number",
+        PropertyCondition::Number(_) => "number",
+        PropertyCondition::Checkbox(_) => "checkbox",
+        PropertyCondition::Select(_) => "select",
+        PropertyCondition::Status(_) => "status",
+        PropertyCondition::MultiSelect(_) => "multi_select",
+        PropertyCondition::Date(_) => "date",
+        PropertyCondition::People(_) => "people",
+        PropertyCondition::Files(_) => "files",
+        PropertyCondition::Relation(_) => "relation",
+        PropertyCondition::Formula(_) => "formula",
+        PropertyCondition::Rollup(_) => "rollup",
+        PropertyCondition::CreatedTime(_) => "created_time",
+        PropertyCondition::LastEditedTime(_) => "last_edited_time",
+        PropertyCondition::CreatedBy(_) => "created_by",
+        PropertyCondition::LastEditedBy(_) => "last_edited_by",
+    }
+}
+
+fn write_text_condition(f: &mut Formatter<'_>, condition: &TextCondition) -> std::fmt::Result {
+    let (operator, value) = match condition {
+        TextCondition::Equals(value) => ("=", value),
+        TextCondition::DoesNotEqual(value) => ("!=", value),
+        TextCondition::Contains(value) => ("contains", value),
+        TextCondition::DoesNotContain(value) => ("not contains", value),
+        TextCondition::StartsWith(value) => ("starts with", value),
+        TextCondition::EndsWith(value) => ("ends with", value),
+        TextCondition::IsEmpty => return f.write_str(" is empty"),
+        TextCondition::IsNotEmpty => return f.write_str(" is not empty"),
+    };
+    write!(f, " {operator} ")?;
+    write_text(f, value)
+}
+
+fn write_number(f: &mut Formatter<'_>, condition: &NumberCondition) -> std::fmt::Result {
+    match condition {
+        NumberCondition::Equals(value) => write!(f, " = {value}"),
+        NumberCondition::DoesNotEqual(value) => write!(f, " != {value}"),
+        NumberCondition::GreaterThan(value) => write!(f, " > {value}"),
+        NumberCondition::LessThan(value) => write!(f, " < {value}"),
+        NumberCondition::GreaterThanOrEqualTo(value) => write!(f, " >= {value}"),
+        NumberCondition::LessThanOrEqualTo(value) => write!(f, " <= {value}"),
+        NumberCondition::IsEmpty => f.write_str(" is empty"),
+        NumberCondition::IsNotEmpty => f.write_str(" is not empty"),
+    }
+}
+
+fn write_checkbox(f: &mut Formatter<'_>, condition: &CheckboxCondition) -> std::fmt::Result {
+    match condition {
+        CheckboxCondition::Equals(value) => write!(f, " = {value}"),
+        CheckboxCondition::DoesNotEqual(value) => write!(f, " != {value}"),
+    }
+}
+
+fn write_date(f: &mut Formatter<'_>, condition: &DateCondition) -> std::fmt::Result {
+    let (operator, date) = match condition {
+        DateCondition::Equals(date) => ("=", date),
+        DateCondition::Before(date) => ("<", date),
+        DateCondition::After(date) => (">", date),
+        DateCondition::OnOrBefore(date) => ("<=", date),
+        DateCondition::OnOrAfter(date) => (">=", date),
+        DateCondition::IsEmpty => return f.write_str(" is empty"),
+        DateCondition::IsNotEmpty => return f.write_str(" is not empty"),
+        relative => {
+            let (name, _) = RELATIVE_DATES
+                .iter()
+                .find(|(_, condition)| condition == relative)
+                .expect("every relative date has a name");
+            return write!(f, " within {name}");
+        }
+    };
+    if date.num_seconds_from_midnight() == 0 && date.timestamp_subsec_nanos() == 0 {
+        write!(f, " {operator} {}", date.format("%Y-%m-%d"))
+    } else {
+        write!(
+            f,
+            " {operator} {}",
+            date.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{self, day};
+    use crate::search::dsl::{last_edited_time, prop};
+    use serde_json::json;
+
+    fn database() -> Database {
+        let rollup = |id: &str, name: &str, function: &str| {
+            json!({"id": id, "name": name, "type": "rollup", "rollup": {
+                "relation_property_name": "Project",
+                "relation_property_id": "p",
+                "rollup_property_name": "Tags",
+                "rollup_property_id": "t",
+                "function": function,
+            }})
+        };
+        fixtures::database(json!({
+            "Name": {"id": "title", "name": "Name", "type": "title", "title": {}},
+            "Story points": {"id": "a", "name": "Story points", "type": "number", "number": {"format": "number"}},
+            "Due": {"id": "b", "name": "Due", "type": "date", "date": {}},
+            "Tags": {"id": "c", "name": "Tags", "type": "multi_select", "multi_select": {"options": []}},
+            "Done": {"id": "d", "name": "Done", "type": "checkbox", "checkbox": {}},
+            "Tasks": rollup("e", "Tasks", "show_original"),
+            "Total": rollup("f", "Total", "sum"),
+            "Latest": rollup("g", "Latest", "latest_date"),
+        }))
+    }
+
+    fn error(input: &str) -> (String, &str) {
+        let error = parse_filter(&database(), input).unwrap_err();
+        (error.message, &input[error.span])
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        let filter = parse_filter(&database(), "Name = a OR Name = b and Name = c").unwrap();
+        let name = |value: &str| prop("Name").title().equals(value);
+        assert_eq!(
+            filter,
+            FilterCondition::Or {
+                or: vec![
+                    name("a"),
+                    FilterCondition::And {
+                        and: vec![name("b"), name("c")]
+                    },
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn errors_point_at_the_input() {
+        assert_eq!(
+            error(r#"Nmae = "x""#),
+            ("no property named \"Nmae\"".to_string(), "Nmae")
+        );
+        assert_eq!(
+            error(r#"Name == "x""#),
+            ("unknown operator".to_string(), "==")
+        );
+        assert_eq!(
+            error(r#"Name equals "x""#),
+            ("expected an operator".to_string(), "equals")
+        );
+        assert_eq!(
+            error(r#"Name = "abc"#),
+            ("unterminated text".to_string(), "\"abc")
+        );
+        let input = "(Name = a";
+        let missing = parse_filter(&database(), input).unwrap_err();
+        assert_eq!(missing.message, "expected `)`");
+        assert_eq!(missing.span, input.len()..input.len());
+        assert_eq!(error("(Name = a b"), ("expected `)`".to_string(), "b"));
+    }
+
+    #[test]
+    fn deep_parentheses_are_rejected() {
+        let input = "(".repeat(200_000);
+        let error = parse_filter(&database(), &input).unwrap_err();
+        assert_eq!(error.span, MAX_DEPTH..MAX_DEPTH + 1);
+        let nested = format!("{}Name = a{}", "(".repeat(5), ")".repeat(5));
+        assert!(parse_filter(&database(), &nested).is_ok());
+    }
+
+    #[test]
+    fn only_two_levels_of_and_or() {
+        let input = "(Name = a OR (Name = b AND (Name = c OR Name = d)))";
+        assert_eq!(
+            error(input),
+            (
+                "AND and OR can only be nested two levels deep".to_string(),
+                "(Name = c OR Name = d)"
+            )
+        );
+        assert!(parse_filter(&database(), "Name = a OR (Name = b AND Name = c)").is_ok());
+    }
+
+    #[test]
+    fn rollups_are_typed() {
+        assert_eq!(
+            error(r#"Tasks any contains "x""#),
+            (
+                "expected the type of the values the rollup collects, like `any number > 3`"
+                    .to_string(),
+                "contains"
+            )
+        );
+        assert_eq!(
+            error(r#"Tasks = "x""#),
+            (
+                "this rollup collects values, compare them with any, every or none".to_string(),
+                "="
+            )
+        );
+        assert_eq!(
+            parse_filter(&database(), "Latest is empty").unwrap(),
+            prop("Latest").rollup().date().is_empty()
+        );
+        assert_eq!(
+            parse_filter(&database(), "Total is empty").unwrap(),
+            prop("Total").rollup().number().is_empty()
+        );
+    }
+
+    #[test]
+    fn display_round_trips() {
+        let database = database();
+        let filters = [
+            prop("Name").title().contains("say \"hi\""),
+            prop("Story points").number().greater_than_or_equal_to(3)
+                | prop("Story points").number().is_empty(),
+            prop("Due").date().before(day("2026-11-01")) & last_edited_time().past_week(),
+            prop("Due")
+                .date()
+                .on_or_after("2026-11-01T09:30:00Z".parse().unwrap()),
+            prop("Tags").multi_select().does_not_contain("infra"),
+            prop("Done").checkbox().equals(true),
+            prop("Tasks").rollup().any().select().equals("x"),
+            prop("Tasks").rollup().every().rich_text().starts_with("a"),
+            prop("Tasks").rollup().none().number().less_than(2),
+            prop("Latest").rollup().date().is_empty(),
+            prop("Total").rollup().number().is_empty(),
+            FilterCondition::And {
+                and: vec![
+                    prop("Name").title().equals("a"),
+                    FilterCondition::And {
+                        and: vec![
+                            prop("Name").title().equals("b"),
+                            prop("Name").title().equals("c"),
+                        ],
+                    },
+                ],
+            },
+            FilterCondition::Or {
+                or: vec![
+                    prop("Name").title().equals("a"),
+                    FilterCondition::Or {
+                        or: vec![
+                            prop("Name").title().equals("b"),
+                            prop("Name").title().equals("c"),
+                        ],
+                    },
+                ],
+            },
+        ];
+        for filter in filters {
+            assert_eq!(
+                parse_filter(&database, &filter.to_string()),
+                Ok(filter.clone()),
+                "{filter}"
+            );
+        }
+    }
+}
@@ -1,21 +1,51 @@
+use crate::pagination::{paginate, FromObject, ListResponse, Paging};
 use crate::user::id::UserId;
+use crate::user::User;
 use crate::{Error, NotionApi, Object};
+use futures::Stream;
 
 const ROUTER: &str = "users";
 
 impl NotionApi {
-    /// list users
-    pub async fn users_list(&self) -> Result<Object, Error> {
-        let u = format!("{}/{}", self.base_path, ROUTER);
-        self.request(self.client.get(u)).await
+    /// list users, at most 100 per request
+    pub async fn users_list(&self, paging: Option<Paging>) -> Result<ListResponse<User>, Error> {
+        self.raw_users_list(paging)
+            .await
+            .and_then(FromObject::from_object)
+    }
+    /// Yield every user of the workspace, fetching more as the stream is polled
+    pub fn users_list_stream(&self) -> impl Stream<Item = Result<User, Error>> + '_ {
+        paginate(move |start_cursor| {
+            let paging = Paging {
+                start_cursor,
+                page_size: None,
+            };
+            async move { self.users_list(Some(paging)).await }
+        })
     }
     /// Retrieve a user
-    pub async fn users_retrieve(&self, id: UserId) -> Result<Object, Error> {
+    pub async fn users_retrieve(&self, id: UserId) -> Result<User, Error> {
+        self.raw_users_retrieve(id)
+            .await
+            .and_then(FromObject::from_object)
+    }
+    /// Retrieve bot's user info
+    pub async fn users_me(&self) -> Result<User, Error> {
+        self.raw_users_me().await.and_then(FromObject::from_object)
+    }
+}
+
+impl NotionApi {
+    pub async fn raw_users_list(&self, paging: Option<Paging>) -> Result<Object, Error> {
+        let u = format!("{}/{}", self.base_path, ROUTER);
+        self.request(self.client.get(u).query(&paging.unwrap_or_default()))
+            .await
+    }
+    pub async fn raw_users_retrieve(&self, id: UserId) -> Result<Object, Error> {
         let u = format!("{}/{}/{}", self.base_path, ROUTER, id);
         self.request(self.client.get(u)).await
     }
-    /// Retrieve bot's user info
-    pub async fn users_me(&self) -> Result<Object, Error> {
+    pub async fn raw_users_me(&self) -> Result<Object, Error> {
         let u = format!("{}/{}/{}", self.base_path, ROUTER, "me");
         self.request(self.client.get(u)).await
     }